@@ -0,0 +1,113 @@
+use core::{slice, str};
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct CommandLine {
+    address: *const u8,
+    length: usize,
+}
+
+impl CommandLine {
+    pub fn new(command_line: &'static str) -> Self {
+        Self {
+            address: command_line.as_ptr(),
+            length: command_line.len(),
+        }
+    }
+
+    pub fn relocate(&mut self, offset: u64) {
+        self.address = (self.address as u64 + offset) as *const u8;
+    }
+
+    /// # Safety
+    ///
+    /// The address must point to `length` readable bytes that stay valid for the rest of the
+    /// program, which holds only in the address space the command line was relocated for.
+    pub unsafe fn as_str(&self) -> &'static str {
+        if self.length == 0 {
+            return "";
+        }
+        let bytes = slice::from_raw_parts(self.address, self.length);
+        str::from_utf8(bytes).unwrap_or("")
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ParsedCommandLine<'a> {
+    text: &'a str,
+}
+
+impl<'a> ParsedCommandLine<'a> {
+    pub const fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn iter(&self) -> Arguments<'a> {
+        Arguments { rest: self.text }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.iter().any(|(argument_key, _)| argument_key == key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.iter()
+            .filter(|&(argument_key, _)| argument_key == key)
+            .last()
+            .map(|(_, value)| value.unwrap_or(""))
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.iter().filter(|&(argument_key, _)| argument_key == key).last()? {
+            (_, None) => Some(true),
+            (_, Some("1")) | (_, Some("true")) | (_, Some("yes")) | (_, Some("on")) => Some(true),
+            (_, Some("0")) | (_, Some("false")) | (_, Some("no")) | (_, Some("off")) => Some(false),
+            _ => None
+        }
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        let value = self.get(key)?;
+        if let Some(hex) = value.strip_prefix("0x") {
+            u64::from_str_radix(hex, 16).ok()
+        } else {
+            value.parse().ok()
+        }
+    }
+}
+
+pub struct Arguments<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Arguments<'a> {
+    type Item = (&'a str, Option<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.rest.trim_start();
+        if text.is_empty() {
+            return None;
+        }
+
+        let mut quoted = false;
+        let end = text.char_indices()
+            .find(|&(_, c)| {
+                if c == '"' {
+                    quoted = !quoted;
+                }
+                c.is_whitespace() && !quoted
+            })
+            .map_or(text.len(), |(index, _)| index);
+        self.rest = &text[end..];
+
+        let argument = &text[..end];
+        let mut key_value = argument.splitn(2, '=');
+        let key = key_value.next().unwrap_or("");
+        let value = key_value.next().map(|value| value.trim_matches('"'));
+        Some((key, value))
+    }
+}
@@ -0,0 +1,32 @@
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PixelFormat {
+    Rgb,
+    Bgr,
+    Bitmask,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PixelBitmask {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+    pub reserved: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FrameBufferInfo {
+    pub base: u64,
+    pub size: usize,
+    pub width: usize,
+    pub height: usize,
+    pub stride: usize,
+    pub pixel_format: PixelFormat,
+    pub pixel_bitmask: PixelBitmask,
+}
+
+impl FrameBufferInfo {
+    pub const BYTES_PER_PIXEL: usize = 4;
+}
@@ -0,0 +1,79 @@
+use core::ops::Deref;
+
+pub const MAX_KERNEL_SEGMENTS: usize = 16;
+pub const KERNEL_HEAP_START: u64 = 0xffff_c000_0000_0000;
+pub const KERNEL_HEAP_MAX_SIZE: u64 = 0x4000_0000;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct KernelSegment {
+    pub start: u64,
+    pub length: u64,
+    pub writable: bool,
+    pub executable: bool,
+}
+
+impl KernelSegment {
+    pub fn end(&self) -> u64 {
+        self.start + self.length
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct KernelStack {
+    pub start: u64,
+    pub end: u64,
+    pub guard_page_start: u64,
+}
+
+impl KernelStack {
+    pub fn size(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn contains(&self, address: u64) -> bool {
+        self.start <= address && address < self.end
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct KernelSegments {
+    segments: [KernelSegment; MAX_KERNEL_SEGMENTS],
+    length: usize,
+}
+
+impl Default for KernelSegments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KernelSegments {
+    pub const fn new() -> Self {
+        Self {
+            segments: [KernelSegment { start: 0, length: 0, writable: false, executable: false };
+                MAX_KERNEL_SEGMENTS],
+            length: 0,
+        }
+    }
+
+    pub fn push(&mut self, segment: KernelSegment) {
+        assert!(self.length < MAX_KERNEL_SEGMENTS, "Too many kernel segments");
+        self.segments[self.length] = segment;
+        self.length += 1;
+    }
+
+    pub fn is_write_xor_execute(&self) -> bool {
+        self.iter().all(|segment| !(segment.writable && segment.executable))
+    }
+}
+
+impl Deref for KernelSegments {
+    type Target = [KernelSegment];
+
+    fn deref(&self) -> &Self::Target {
+        &self.segments[..self.length]
+    }
+}
@@ -1,15 +1,66 @@
 #![no_std]
 
+pub mod command_line;
+pub mod frame_buffer;
+pub mod kernel;
+pub mod memory;
+pub mod module;
+pub mod note;
+
+pub const BOOT_PROTOCOL_VERSION: u32 = 5;
+pub const BOOT_INFO_MAGIC: u64 = u64::from_le_bytes(*b"MonorsBI");
+
 #[cfg(target_arch = "x86_64")]
-pub type KernelEntryFunction = extern "sysv64" fn(BootInfo);
+pub type KernelEntryFunction = extern "sysv64" fn(&'static BootInfo) -> !;
 
-use uefi_wrapper::protocols::console::text_output::SimpleTextOutputProtocol;
+use crate::command_line::CommandLine;
+use crate::frame_buffer::FrameBufferInfo;
+use crate::kernel::{KernelSegments, KernelStack};
+use crate::memory::{MemoryRegions, PageTableInfo};
+use crate::module::Modules;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct BootInfoHeader {
+    pub magic: u64,
+    pub version: u32,
+    pub size: u32,
+}
+
+impl Default for BootInfoHeader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BootInfoHeader {
+    pub const fn new() -> Self {
+        Self {
+            magic: BOOT_INFO_MAGIC,
+            version: BOOT_PROTOCOL_VERSION,
+            size: core::mem::size_of::<BootInfo>() as u32,
+        }
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.magic == BOOT_INFO_MAGIC
+            && self.version == BOOT_PROTOCOL_VERSION
+            && self.size as usize == core::mem::size_of::<BootInfo>()
+    }
+}
 
 #[repr(C)]
 pub struct BootInfo {
-    // pub memory_map: MemoryMap<'a>,
+    pub header: BootInfoHeader,
+    pub memory_regions: MemoryRegions,
+    pub frame_buffer: FrameBufferInfo,
+    pub kernel_segments: KernelSegments,
+    pub kernel_stack: KernelStack,
+    pub kernel_slide: u64,
+    pub page_tables: PageTableInfo,
+    pub physical_memory_offset: u64,
+    pub command_line: CommandLine,
+    pub modules: Modules,
     // pub runtime_services: &'static RuntimeServices,
     // pub configuration_table: &'static [ConfigurationTable],
-
-    pub con_out: &'static mut SimpleTextOutputProtocol,
 }
@@ -0,0 +1,91 @@
+use core::ops::Deref;
+use core::slice;
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MemoryRegionKind {
+    Usable,
+    BootloaderReclaimable,
+    BootServicesReclaimable,
+    UefiRuntimeServices,
+    AcpiReclaimable,
+    AcpiNvs,
+    Mmio,
+    KernelImage,
+    PageTables,
+    KernelStack,
+    Module,
+    Reserved,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct PageTableInfo {
+    pub pml4_table_address: u64,
+    pub physical_memory_size: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct MemoryRegion {
+    pub start: u64,
+    pub length: u64,
+    pub kind: MemoryRegionKind,
+}
+
+impl MemoryRegion {
+    pub fn end(&self) -> u64 {
+        self.start + self.length
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct MemoryRegions {
+    regions: *const MemoryRegion,
+    length: usize,
+}
+
+impl MemoryRegions {
+    pub fn new(regions: &'static [MemoryRegion]) -> Self {
+        Self {
+            regions: regions.as_ptr(),
+            length: regions.len(),
+        }
+    }
+
+    pub fn relocate(&mut self, offset: u64) {
+        self.regions = (self.regions as u64 + offset) as *const MemoryRegion;
+    }
+
+    // Sorts the regions by address and merges adjacent regions of the same kind in place,
+    // dropping empty ones.
+    pub fn from_regions(regions: &'static mut [MemoryRegion]) -> Self {
+        regions.sort_unstable_by_key(|region| region.start);
+        let mut length = 0;
+        for i in 0..regions.len() {
+            let region = regions[i];
+            if region.length == 0 {
+                continue;
+            }
+            if length > 0
+                && regions[length - 1].kind == region.kind
+                && regions[length - 1].end() == region.start {
+                regions[length - 1].length += region.length;
+            } else {
+                regions[length] = region;
+                length += 1;
+            }
+        }
+        let regions: &'static [MemoryRegion] = regions;
+        Self::new(&regions[..length])
+    }
+}
+
+impl Deref for MemoryRegions {
+    type Target = [MemoryRegion];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.regions, self.length) }
+    }
+}
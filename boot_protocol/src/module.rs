@@ -0,0 +1,91 @@
+use core::ops::Deref;
+use core::{slice, str};
+
+pub const MAX_MODULES: usize = 16;
+pub const MAX_MODULE_NAME_LENGTH: usize = 64;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Module {
+    name: [u8; MAX_MODULE_NAME_LENGTH],
+    name_length: usize,
+    pub physical_address: u64,
+    pub size: u64,
+}
+
+impl Module {
+    pub fn new(name: &str, physical_address: u64, size: u64) -> Self {
+        assert!(name.len() <= MAX_MODULE_NAME_LENGTH, "Module name is too long: {}", name);
+        let mut module = Self {
+            name: [0; MAX_MODULE_NAME_LENGTH],
+            name_length: name.len(),
+            physical_address,
+            size,
+        };
+        module.name[..name.len()].copy_from_slice(name.as_bytes());
+        module
+    }
+
+    pub fn name(&self) -> &str {
+        str::from_utf8(&self.name[..self.name_length]).unwrap_or("")
+    }
+
+    pub fn end(&self) -> u64 {
+        self.physical_address + self.size
+    }
+
+    /// # Safety
+    ///
+    /// All physical memory must be mapped at `physical_memory_offset` for the rest of the program.
+    pub unsafe fn data(&self, physical_memory_offset: u64) -> &'static [u8] {
+        slice::from_raw_parts(
+            (self.physical_address + physical_memory_offset) as *const u8,
+            self.size as usize,
+        )
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct Modules {
+    modules: [Module; MAX_MODULES],
+    length: usize,
+}
+
+impl Default for Modules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Modules {
+    pub const fn new() -> Self {
+        Self {
+            modules: [Module {
+                name: [0; MAX_MODULE_NAME_LENGTH],
+                name_length: 0,
+                physical_address: 0,
+                size: 0,
+            }; MAX_MODULES],
+            length: 0,
+        }
+    }
+
+    pub fn push(&mut self, module: Module) {
+        assert!(self.length < MAX_MODULES, "Too many modules");
+        self.modules[self.length] = module;
+        self.length += 1;
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Module> {
+        self.iter().find(|module| module.name() == name)
+    }
+}
+
+impl Deref for Modules {
+    type Target = [Module];
+
+    fn deref(&self) -> &Self::Target {
+        &self.modules[..self.length]
+    }
+}
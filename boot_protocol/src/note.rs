@@ -0,0 +1,28 @@
+pub const NOTE_NAME: [u8; 12] = *b"MonorsOS\0\0\0\0";
+pub const NOTE_NAME_SIZE: u32 = 9;
+pub const BOOT_PROTOCOL_VERSION_NOTE_TYPE: u32 = 1;
+
+#[repr(C, align(4))]
+pub struct BootProtocolVersionNote {
+    name_size: u32,
+    descriptor_size: u32,
+    note_type: u32,
+    name: [u8; 12],
+    version: u32,
+}
+
+impl BootProtocolVersionNote {
+    pub const fn new(version: u32) -> Self {
+        Self {
+            name_size: NOTE_NAME_SIZE,
+            descriptor_size: core::mem::size_of::<u32>() as u32,
+            note_type: BOOT_PROTOCOL_VERSION_NOTE_TYPE,
+            name: NOTE_NAME,
+            version,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
@@ -0,0 +1,71 @@
+use boot_protocol::command_line::ParsedCommandLine;
+
+#[test]
+fn empty_command_line_has_no_arguments() {
+    let command_line = ParsedCommandLine::new("   ");
+    assert_eq!(command_line.iter().count(), 0);
+    assert!(!command_line.contains("debug"));
+    assert_eq!(command_line.get("debug"), None);
+}
+
+#[test]
+fn splits_keys_and_values() {
+    let command_line = ParsedCommandLine::new("  debug loglevel=7\troot=/dev/sda1 empty= ");
+    let arguments = command_line.iter().collect::<Vec<_>>();
+    assert_eq!(arguments, [
+        ("debug", None),
+        ("loglevel", Some("7")),
+        ("root", Some("/dev/sda1")),
+        ("empty", Some("")),
+    ]);
+    assert!(command_line.contains("debug"));
+    assert_eq!(command_line.get("debug"), Some(""));
+    assert_eq!(command_line.get("root"), Some("/dev/sda1"));
+}
+
+#[test]
+fn keeps_quoted_whitespace_in_values() {
+    let command_line = ParsedCommandLine::new("title=\"Monors OS\" quiet");
+    assert_eq!(command_line.get("title"), Some("Monors OS"));
+    assert!(command_line.contains("quiet"));
+}
+
+#[test]
+fn keeps_equals_signs_in_values() {
+    let command_line = ParsedCommandLine::new("init=/bin/sh=x");
+    assert_eq!(command_line.get("init"), Some("/bin/sh=x"));
+}
+
+#[test]
+fn last_key_wins() {
+    let command_line = ParsedCommandLine::new("loglevel=3 debug=off loglevel=7 debug");
+    assert_eq!(command_line.get("loglevel"), Some("7"));
+    assert_eq!(command_line.get_u64("loglevel"), Some(7));
+    assert_eq!(command_line.get_bool("debug"), Some(true));
+}
+
+#[test]
+fn parses_booleans() {
+    let command_line =
+        ParsedCommandLine::new("a b=1 c=true d=yes e=on f=0 g=false h=no i=off j=maybe");
+    for key in ["a", "b", "c", "d", "e"].iter() {
+        assert_eq!(command_line.get_bool(key), Some(true), "{}", key);
+    }
+    for key in ["f", "g", "h", "i"].iter() {
+        assert_eq!(command_line.get_bool(key), Some(false), "{}", key);
+    }
+    assert_eq!(command_line.get_bool("j"), None);
+    assert_eq!(command_line.get_bool("missing"), None);
+}
+
+#[test]
+fn parses_decimal_and_hex_numbers() {
+    let command_line = ParsedCommandLine::new("a=42 b=0x2a c=0xffffffffffffffff d=0x e=-1 f=12k");
+    assert_eq!(command_line.get_u64("a"), Some(42));
+    assert_eq!(command_line.get_u64("b"), Some(0x2a));
+    assert_eq!(command_line.get_u64("c"), Some(u64::MAX));
+    assert_eq!(command_line.get_u64("d"), None);
+    assert_eq!(command_line.get_u64("e"), None);
+    assert_eq!(command_line.get_u64("f"), None);
+    assert_eq!(command_line.get_u64("missing"), None);
+}
@@ -0,0 +1,47 @@
+use boot_protocol::memory::{MemoryRegion, MemoryRegionKind, MemoryRegions};
+
+fn region(start: u64, length: u64, kind: MemoryRegionKind) -> MemoryRegion {
+    MemoryRegion { start, length, kind }
+}
+
+fn from_regions(regions: Vec<MemoryRegion>) -> MemoryRegions {
+    MemoryRegions::from_regions(Box::leak(regions.into_boxed_slice()))
+}
+
+fn summary(regions: &MemoryRegions) -> Vec<(u64, u64, MemoryRegionKind)> {
+    regions.iter().map(|region| (region.start, region.length, region.kind)).collect()
+}
+
+#[test]
+fn sorts_and_merges_adjacent_regions_of_the_same_kind() {
+    let regions = from_regions(vec![
+        region(0x3000, 0x1000, MemoryRegionKind::Usable),
+        region(0x0000, 0x1000, MemoryRegionKind::Reserved),
+        region(0x1000, 0x1000, MemoryRegionKind::Usable),
+        region(0x2000, 0x1000, MemoryRegionKind::Usable),
+        region(0x5000, 0x1000, MemoryRegionKind::Usable),
+    ]);
+    assert_eq!(summary(&regions), [
+        (0x0000, 0x1000, MemoryRegionKind::Reserved),
+        (0x1000, 0x3000, MemoryRegionKind::Usable),
+        (0x5000, 0x1000, MemoryRegionKind::Usable),
+    ]);
+}
+
+#[test]
+fn drops_empty_regions() {
+    let regions = from_regions(vec![
+        region(0x0000, 0, MemoryRegionKind::Reserved),
+        region(0x0000, 0x1000, MemoryRegionKind::Usable),
+        region(0x1000, 0, MemoryRegionKind::Mmio),
+        region(0x1000, 0x1000, MemoryRegionKind::Usable),
+    ]);
+    assert_eq!(summary(&regions), [(0x0000, 0x2000, MemoryRegionKind::Usable)]);
+}
+
+#[test]
+fn empty_regions_only() {
+    let regions = from_regions(vec![region(0x1000, 0, MemoryRegionKind::Usable)]);
+    assert!(regions.is_empty());
+    assert!(from_regions(Vec::new()).is_empty());
+}
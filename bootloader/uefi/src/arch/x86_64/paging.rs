@@ -1,88 +1,84 @@
-use uefi_wrapper::memory::{ALLOCATE_PAGE_SIZE, AllocateType, MemoryType};
+use uefi_wrapper::memory::{ALLOCATE_PAGE_SIZE, AllocateType};
 use x86_64::address::*;
 use x86_64::control::*;
-use x86_64::paging::*;
-use x86_64::paging::page::*;
-use x86_64::paging::page_directory::*;
-use x86_64::paging::pdp::*;
-use x86_64::paging::pml4::*;
+use x86_64::paging::mapper::{FrameAllocator, Mapper};
+use x86_64::paging::page::PTEntryFlags;
 
-pub const PAGE_SIZE: usize = ALLOCATE_PAGE_SIZE;
+use crate::memory::PAGE_TABLES_MEMORY_TYPE;
 
-pub unsafe fn map_page(virtual_start: usize, page_address: usize) {
-    let virtual_address = VirtualAddress::new(virtual_start as u64);
-    let page_address = PhysicalAddress::new(page_address as u64);
-    assert!(page_address.is_aligned(PAGE_SIZE_4KB));
+pub const PAGE_SIZE: usize = ALLOCATE_PAGE_SIZE;
 
-    CR0::read().set_write_protect(false);
+struct PageTableAllocator;
 
-    map_page_inner(virtual_address, page_address);
+impl FrameAllocator for PageTableAllocator {
+    fn allocate_frame(&mut self) -> Option<PhysicalAddress> {
+        crate::boot_services().allocate_pages(AllocateType::AnyPages, PAGE_TABLES_MEMORY_TYPE, 1)
+            .ok()
+            .map(|address| PhysicalAddress::new(address.0))
+    }
+}
 
-    CR0::read().set_write_protect(true);
+pub unsafe fn enable_no_execute() {
+    EFER::read().set_no_execute_enable(true);
 }
 
-fn allocate_page() -> u64 {
-    crate::boot_services().allocate_pages(
-        AllocateType::AnyPages, MemoryType::LoaderData, 1)
-        .expect("Could not allocate page").0
+pub struct PageTables {
+    mapper: Mapper,
 }
 
-unsafe fn map_page_inner(virtual_address: VirtualAddress, page_address: PhysicalAddress) {
-    let mut table = PML4Table::from_address(CR3::read().pml4_table_address());
-    let entry = &mut table[virtual_address.pml4_table_index()];
-    if !entry.flags().contains(PML4EntryFlags::PRESENT) {
-        let allocate_page = PhysicalAddress::new(allocate_page());
-        core::ptr::write_bytes(
-            allocate_page.as_mut_ptr::<u8>(),
-            0,
-            ALLOCATE_PAGE_SIZE,
-        );
-        entry.set_unused();
-        entry.set_page(allocate_page);
-        entry.set_flags(PML4EntryFlags::PRESENT | PML4EntryFlags::WRITABLE);
+impl PageTables {
+    pub fn new() -> Self {
+        let pml4_table_address = PageTableAllocator.allocate_frame()
+            .expect("Could not allocate PML4 table");
+        unsafe {
+            core::ptr::write_bytes(pml4_table_address.as_mut_ptr::<u8>(), 0, PAGE_SIZE);
+            Self { mapper: Mapper::new(pml4_table_address, 0) }
+        }
     }
 
-    let mut table = PDPTable::from_address(entry.address());
-    let entry = &mut table[virtual_address.pdp_table_index()];
-    if entry.flags().contains(PDPTEntryFlags::PAGE_SIZE) {
-        panic!("Virtual address was already exist: {:#x}", virtual_address.as_u64());
+    pub fn pml4_table_address(&self) -> PhysicalAddress {
+        self.mapper.pml4_table_address()
     }
-    if !entry.flags().contains(PDPTEntryFlags::PRESENT) {
-        let allocate_page = PhysicalAddress::new(allocate_page());
-        core::ptr::write_bytes(
-            allocate_page.as_mut_ptr::<u8>(),
-            0,
-            ALLOCATE_PAGE_SIZE,
-        );
-        entry.set_unused();
-        entry.set_page(allocate_page);
-        entry.set_flags(PDPTEntryFlags::PRESENT | PDPTEntryFlags::WRITABLE);
+
+    pub fn map_page(&mut self, virtual_start: usize, page_address: usize, flags: PTEntryFlags) {
+        let virtual_address = VirtualAddress::new(virtual_start as u64);
+        let page_address = PhysicalAddress::new(page_address as u64);
+        if let Err(error) = unsafe {
+            self.mapper.map(virtual_address, page_address, flags, &mut PageTableAllocator)
+        } {
+            panic!("Could not map page {:#x}: {:?}", virtual_address.as_u64(), error);
+        }
     }
 
-    let mut table = PageDirectory::from_address(entry.address());
-    let entry: &mut PDEntry = &mut table[virtual_address.pd_table_index()];
-    if entry.flags().contains(PDEntryFlags::PAGE_SIZE) {
-        panic!("Virtual address was already exist: {:#x}", virtual_address.as_u64());
+    pub fn identity_map(&mut self, start: u64, length: u64, flags: PTEntryFlags) {
+        if let Err(error) = unsafe {
+            self.mapper.map_range(
+                VirtualAddress::new(start),
+                PhysicalAddress::new(start),
+                length,
+                flags,
+                &mut PageTableAllocator,
+            )
+        } {
+            panic!("Could not identity map {:#x}-{:#x}: {:?}", start, start + length, error);
+        }
     }
-    if !entry.flags().contains(PDEntryFlags::PRESENT) {
-        let allocate_page = PhysicalAddress::new(allocate_page());
-        core::ptr::write_bytes(
-            allocate_page.as_mut_ptr::<u8>(),
-            0,
-            ALLOCATE_PAGE_SIZE,
-        );
-        entry.set_unused();
-        entry.set_page(allocate_page);
-        entry.set_flags(PDEntryFlags::PRESENT | PDEntryFlags::WRITABLE);
+
+    pub fn map_physical_memory(&mut self, offset: u64, length: u64) {
+        if let Err(error) = unsafe {
+            self.mapper.map_range(
+                VirtualAddress::new(offset),
+                PhysicalAddress::new(0),
+                length,
+                PTEntryFlags::PRESENT | PTEntryFlags::WRITABLE | PTEntryFlags::EXECUTE_DISABLE,
+                &mut PageTableAllocator,
+            )
+        } {
+            panic!("Could not map physical memory at {:#x}: {:?}", offset, error);
+        }
     }
 
-    let mut table = PageTable::from_address(entry.address());
-    let entry = &mut table[virtual_address.page_table_index()];
-    if entry.flags().contains(PTEntryFlags::PRESENT) {
-        panic!("Virtual address was already exist: {:#x}", virtual_address.as_u64());
-    } else {
-        entry.set_unused();
-        entry.set_page(page_address);
-        entry.set_flags(PTEntryFlags::PRESENT | PTEntryFlags::WRITABLE);
+    pub unsafe fn activate(&self) {
+        CR3::read().set_pml4_table_address(self.pml4_table_address());
     }
 }
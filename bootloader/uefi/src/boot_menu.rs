@@ -1,26 +1,44 @@
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::ffi::c_void;
 
 use uefi_wrapper::{Event, print, println};
 use uefi_wrapper::boot_services::{EventType, TPL};
+use uefi_wrapper::protocols::console::text_input::{InputKey, ScanCode};
 use uefi_wrapper::protocols::console::text_output::Geometry;
 use uefi_wrapper::time::TimerDelay;
 
 use crate::*;
 
+const NORMAL_ATTRIBUTE: usize = 0x07;
+const HIGHLIGHT_ATTRIBUTE: usize = 0x70;
+const BACKSPACE: u16 = 0x08;
+const COMMAND_LINE_LABEL: &str = "Command line: ";
+
 pub struct BootMenuOption {
-    name: &'static str,
+    name: String,
+    command_line: Option<String>,
     action: fn(),
 }
 
 impl BootMenuOption {
-    pub fn new(name: &'static str, action: fn()) -> BootMenuOption {
+    pub fn new(name: &str, action: fn()) -> BootMenuOption {
         Self {
-            name,
+            name: name.to_string(),
+            command_line: None,
             action,
         }
     }
 
+    pub fn with_command_line(mut self, command_line: &str) -> BootMenuOption {
+        self.command_line = Some(command_line.to_string());
+        self
+    }
+
+    pub fn command_line(&self) -> Option<&str> {
+        self.command_line.as_deref()
+    }
+
     fn action(&self) {
         (self.action)();
     }
@@ -33,77 +51,180 @@ enum ReceiveFrom {
 
 pub struct BootMenu {
     options: Vec<BootMenuOption>,
+    selected: usize,
+    default_option: usize,
+    options_cursor: Geometry,
+    command_line_cursor: Geometry,
     count_down_cursor: Geometry,
-    press_key_cursor: Geometry,
     automatic_boot_start_cursor: Geometry,
-    automatic_boot_end_cursor: Geometry,
-    count: i32,
+    end_cursor: Geometry,
+    count: u32,
     timeout_event: Option<Event>,
     timer_event: Option<Event>,
 }
 
 impl BootMenu {
-    pub fn new(auto_boot_seconds: i32, options: Vec<BootMenuOption>) -> Self {
+    pub fn new(auto_boot_seconds: u32, default_option: usize, options: Vec<BootMenuOption>)
+               -> Self {
         assert!(auto_boot_seconds > 0);
-        assert!(auto_boot_seconds < 10);
         assert!(options.len() > 0);
+        assert!(default_option < options.len());
         BootMenu {
             options,
+            selected: default_option,
+            default_option,
+            options_cursor: Geometry::default(),
+            command_line_cursor: Geometry::default(),
             count_down_cursor: Geometry::default(),
-            press_key_cursor: Geometry::default(),
             automatic_boot_start_cursor: Geometry::default(),
-            automatic_boot_end_cursor: Geometry::default(),
+            end_cursor: Geometry::default(),
             count: auto_boot_seconds,
             timeout_event: None,
             timer_event: None,
         }
     }
 
-    pub fn menu_loop(&mut self) {
+    pub fn option(&self, index: usize) -> &BootMenuOption {
+        &self.options[index]
+    }
+
+    pub fn menu_loop(&mut self) -> usize {
         self.print_options();
         println!();
-        self.print_press_key();
-        println!("\n");
+        self.command_line_cursor = BootMenu::current_cursor();
+        self.print_command_line();
+        println!();
+        println!("Use Up/Down to select, Enter to boot, 'e' to edit the command line.");
+        println!();
         self.print_automatic_boot();
 
         self.set_count_down();
 
-        let mut select_option = 1;
         match self.wait_event() {
             ReceiveFrom::Keyboard => {
                 self.clear_timer();
-                BootMenu::clear_characters(self.automatic_boot_start_cursor, self.automatic_boot_end_cursor);
+                BootMenu::clear_characters(self.automatic_boot_start_cursor, self.end_cursor);
 
-                let current_cursor = BootMenu::current_cursor();
                 loop {
-                    let input_char = BootMenu::wait_for_input_char() as u8 as char;
-                    if input_char >= '1'
-                        && input_char <= ('0' as usize + self.options.len()) as u8 as char {
-                        BootMenu::set_cursor(self.press_key_cursor);
-                        select_option = input_char as usize - '0' as usize;
-                        print!("{}", input_char);
-                    } else if input_char == '\r' || input_char == '\n' {
-                        break;
+                    let key = BootMenu::wait_for_key();
+                    match (key.scan_code(), key.unicode_char()) {
+                        (ScanCode::UP, _) => self.select(self.selected.saturating_sub(1)),
+                        (ScanCode::DOWN, _) => self.select(self.selected + 1),
+                        (ScanCode::HOME, _) => self.select(0),
+                        (ScanCode::END, _) => self.select(self.options.len() - 1),
+                        (ScanCode::ESCAPE, _) => self.select(self.default_option),
+                        (_, c) if c == '\r' as u16 || c == '\n' as u16 => break,
+                        (_, c) if c == 'e' as u16 => self.edit_command_line(),
+                        (_, c) if c >= '1' as u16 && c <= '9' as u16 => {
+                            self.select((c - '1' as u16) as usize)
+                        }
+                        _ => {}
                     }
                 }
-                BootMenu::set_cursor(current_cursor);
             }
             ReceiveFrom::Timeout => {}
         }
-        self.options[select_option - 1].action();
+        BootMenu::set_cursor(self.end_cursor);
+        self.options[self.selected].action();
+        self.selected
     }
 
-    fn print_options(&self) {
+    fn print_options(&mut self) {
         println!("Select option: ");
-        for (i, option) in self.options.iter().enumerate() {
-            println!("     {}: {}", i + 1, option.name);
+        self.options_cursor = BootMenu::current_cursor();
+        for i in 0..self.options.len() {
+            self.print_option(i);
+            println!();
         }
     }
 
-    fn print_press_key(&mut self) {
-        print!("Press key: [ ");
-        self.press_key_cursor = BootMenu::current_cursor();
-        println!("  ]");
+    fn print_option(&self, index: usize) {
+        let width = self.options.iter()
+            .map(|option| option.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        BootMenu::set_cursor(Geometry::new(0, self.options_cursor.row() + index));
+        if index == self.selected {
+            BootMenu::set_attribute(HIGHLIGHT_ATTRIBUTE);
+        }
+        print!("  {:>2}: {:<width$}  ", index + 1, self.options[index].name, width = width);
+        BootMenu::set_attribute(NORMAL_ATTRIBUTE);
+    }
+
+    fn select(&mut self, index: usize) {
+        if index >= self.options.len() || index == self.selected {
+            return;
+        }
+        let previous = self.selected;
+        self.selected = index;
+        self.print_option(previous);
+        self.print_option(index);
+        self.print_command_line();
+    }
+
+    fn print_command_line(&self) {
+        BootMenu::clear_line(self.command_line_cursor.row());
+        BootMenu::set_cursor(self.command_line_cursor);
+        if let Some(command_line) = self.options[self.selected].command_line() {
+            let visible = command_line.chars().take(self.command_line_width()).collect::<String>();
+            print!("{}{}", COMMAND_LINE_LABEL, visible);
+        }
+    }
+
+    // The command line is kept on a single row and scrolled horizontally, so it never runs into
+    // the lines printed below it.
+    fn command_line_width(&self) -> usize {
+        (BootMenu::screen().column() - 1)
+            .saturating_sub(self.command_line_cursor.column() + COMMAND_LINE_LABEL.len())
+    }
+
+    fn edit_command_line(&mut self) {
+        let mut command_line: Vec<char> = match self.options[self.selected].command_line() {
+            Some(command_line) => command_line.chars().collect(),
+            None => return,
+        };
+        let start = Geometry::new(
+            self.command_line_cursor.column() + COMMAND_LINE_LABEL.len(),
+            self.command_line_cursor.row(),
+        );
+        let width = self.command_line_width();
+        let mut position = command_line.len();
+        let mut scroll = 0;
+        BootMenu::print_scrolled(start, &command_line, position, &mut scroll, width);
+        BootMenu::enable_cursor(true);
+
+        let accepted = loop {
+            let key = BootMenu::wait_for_key();
+            match (key.scan_code(), key.unicode_char()) {
+                (ScanCode::ESCAPE, _) => break false,
+                (ScanCode::LEFT, _) => position = position.saturating_sub(1),
+                (ScanCode::RIGHT, _) => position = (position + 1).min(command_line.len()),
+                (ScanCode::HOME, _) => position = 0,
+                (ScanCode::END, _) => position = command_line.len(),
+                (ScanCode::DELETE, _) if position < command_line.len() => {
+                    command_line.remove(position);
+                }
+                (_, c) if c == '\r' as u16 || c == '\n' as u16 => break true,
+                (_, BACKSPACE) if position > 0 => {
+                    position -= 1;
+                    command_line.remove(position);
+                }
+                (_, c) if c >= ' ' as u16 => {
+                    if let Some(c) = core::char::from_u32(c as u32) {
+                        command_line.insert(position, c);
+                        position += 1;
+                    }
+                }
+                _ => {}
+            }
+            BootMenu::print_scrolled(start, &command_line, position, &mut scroll, width);
+        };
+
+        BootMenu::enable_cursor(false);
+        if accepted {
+            self.options[self.selected].command_line = Some(command_line.into_iter().collect());
+        }
+        self.print_command_line();
     }
 
     fn print_automatic_boot(&mut self) {
@@ -111,14 +232,40 @@ impl BootMenu {
         print!("Automatic boot in ");
         self.count_down_cursor = BootMenu::current_cursor();
         println!("{} seconds...", self.count);
-        self.automatic_boot_end_cursor = BootMenu::current_cursor();
+        self.end_cursor = BootMenu::current_cursor();
+    }
+
+    fn screen() -> Geometry {
+        con_out().query_mode(
+            con_out().mode().mode_number() as usize
+        ).unwrap()
+    }
+
+    fn print_scrolled(start: Geometry, text: &[char], position: usize, scroll: &mut usize,
+                      width: usize) {
+        if position < *scroll {
+            *scroll = position;
+        } else if position > *scroll + width {
+            *scroll = position - width;
+        }
+        let visible = text[*scroll..].iter().take(width).collect::<String>();
+        BootMenu::set_cursor(start);
+        print!("{:<width$}", visible, width = width);
+        BootMenu::set_cursor(Geometry::new(start.column() + position - *scroll, start.row()));
+    }
+
+    fn clear_line(row: usize) {
+        let current_cursor = BootMenu::current_cursor();
+        BootMenu::set_cursor(Geometry::new(0, row));
+        for _ in 0..BootMenu::screen().column() - 1 {
+            print!(" ");
+        }
+        BootMenu::set_cursor(current_cursor);
     }
 
     fn clear_characters(start_cursor: Geometry, end_cursor: Geometry) {
         assert!(start_cursor.row() < end_cursor.row());
-        let screen = con_out().query_mode(
-            con_out().mode().mode_number() as usize
-        ).unwrap();
+        let screen = BootMenu::screen();
 
         let current_cursor = BootMenu::current_cursor();
         BootMenu::set_cursor(start_cursor);
@@ -159,13 +306,13 @@ impl BootMenu {
     }
 
     unsafe extern "efiapi" fn count_down_boot_timer(_event: Event, context: *mut c_void) {
-        let mut boot_menu = (context as *mut BootMenu).as_mut()
+        let boot_menu = (context as *mut BootMenu).as_mut()
             .expect("Could not access BootMenu");
         boot_menu.count -= 1;
 
         let current_cursor = BootMenu::current_cursor();
         BootMenu::set_cursor(boot_menu.count_down_cursor);
-        print!("{}", boot_menu.count);
+        print!("{} seconds...  ", boot_menu.count);
 
         BootMenu::set_cursor(current_cursor);
 
@@ -211,15 +358,14 @@ impl BootMenu {
         }
     }
 
-    fn wait_for_input_char() -> u16 {
+    fn wait_for_key() -> InputKey {
         match con_in().input_read_key() {
-            Ok(key) => key.unicode_char(),
+            Ok(key) => key,
             Err(_) => {
                 boot_services().wait_for_event(&[con_in().wait_for_key()])
                     .expect("Could not set wait_for_event");
                 con_in().input_read_key()
                     .expect("Could not read input key")
-                    .unicode_char()
             }
         }
     }
@@ -233,4 +379,13 @@ impl BootMenu {
         con_out().set_cursor_position(cursor)
             .expect("Could not set cursor");
     }
+
+    fn set_attribute(attribute: usize) {
+        con_out().set_attribute(attribute)
+            .expect("Could not set attribute");
+    }
+
+    fn enable_cursor(visible: bool) {
+        let _ = con_out().enable_cursor(visible);
+    }
 }
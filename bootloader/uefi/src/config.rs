@@ -0,0 +1,174 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str;
+
+use boot_protocol::module::{MAX_MODULE_NAME_LENGTH, MAX_MODULES};
+use uefi_wrapper::println;
+use x86_64::paging::{PAGE_SIZE_2MB, PAGE_SIZE_4KB};
+
+use crate::HIGHER_HALF_START;
+use crate::protocol::file::read_file;
+
+pub const CONFIG_PATH: &str = "\\boot\\monors.cfg";
+pub const DEFAULT_KERNEL_PATH: &str = "\\boot\\kernel";
+const DEFAULT_ENTRY_NAME: &str = "Boot MonorsOS";
+const DEFAULT_TIMEOUT: u32 = 1;
+const DEFAULT_RESOLUTION: (u32, u32) = (1024, 768);
+const DEFAULT_PHYSICAL_MEMORY_OFFSET: u64 = 0xffff_8000_0000_0000;
+const DEFAULT_KERNEL_STACK_SIZE: u64 = 0x2_0000;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    InvalidUtf8,
+    InvalidLine(usize),
+    UnknownKey(usize),
+    InvalidValue(usize),
+    TooManyModules(usize),
+    ModulePathTooLong(usize),
+    NoEntries,
+    DefaultEntryOutOfRange(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct BootEntry {
+    pub name: String,
+    pub kernel: String,
+    pub command_line: String,
+    pub modules: Vec<String>,
+}
+
+impl BootEntry {
+    fn new() -> Self {
+        Self {
+            name: DEFAULT_ENTRY_NAME.to_string(),
+            kernel: DEFAULT_KERNEL_PATH.to_string(),
+            command_line: String::new(),
+            modules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Config {
+    pub timeout: u32,
+    pub default_entry: usize,
+    pub resolution: (u32, u32),
+    pub physical_memory_offset: u64,
+    pub kernel_stack_size: u64,
+    pub entries: Vec<BootEntry>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            default_entry: 0,
+            resolution: DEFAULT_RESOLUTION,
+            physical_memory_offset: DEFAULT_PHYSICAL_MEMORY_OFFSET,
+            kernel_stack_size: DEFAULT_KERNEL_STACK_SIZE,
+            entries: vec![BootEntry::new()],
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let file = match read_file(CONFIG_PATH) {
+            Ok(file) => file,
+            Err(_) => {
+                println!("{} not found, using default configuration", CONFIG_PATH);
+                return Self::default();
+            }
+        };
+        match Self::parse(&file) {
+            Ok(config) => config,
+            Err(error) => {
+                println!("Could not parse {}: {:?}, using default configuration",
+                         CONFIG_PATH, error);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn parse(file: &[u8]) -> Result<Self, ConfigError> {
+        let text = str::from_utf8(file).map_err(|_| ConfigError::InvalidUtf8)?;
+        let mut config = Self {
+            entries: Vec::new(),
+            ..Self::default()
+        };
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line == "[entry]" {
+                config.entries.push(BootEntry::new());
+                continue;
+            }
+
+            let mut key_value = line.splitn(2, '=');
+            let key = key_value.next().unwrap_or("").trim();
+            let value = key_value.next().ok_or(ConfigError::InvalidLine(line_number))?.trim();
+            match (config.entries.last_mut(), key) {
+                (None, "timeout") => config.timeout = value.parse()
+                    .map_err(|_| ConfigError::InvalidValue(line_number))?,
+                (None, "default") => config.default_entry = value.parse()
+                    .map_err(|_| ConfigError::InvalidValue(line_number))?,
+                (None, "resolution") => config.resolution = parse_resolution(value)
+                    .ok_or(ConfigError::InvalidValue(line_number))?,
+                (None, "physical_memory_offset") => config.physical_memory_offset =
+                    parse_number(value)
+                        .filter(|&offset| offset >= HIGHER_HALF_START)
+                        .filter(|&offset| offset % PAGE_SIZE_2MB == 0)
+                        .ok_or(ConfigError::InvalidValue(line_number))?,
+                (None, "kernel_stack_size") => config.kernel_stack_size = parse_number(value)
+                    .filter(|&size| size != 0 && size % PAGE_SIZE_4KB == 0)
+                    .ok_or(ConfigError::InvalidValue(line_number))?,
+                (Some(entry), "name") => entry.name = value.to_string(),
+                (Some(entry), "kernel") => entry.kernel = to_uefi_path(value),
+                (Some(entry), "cmdline") => entry.command_line = value.to_string(),
+                (Some(entry), "module") | (Some(entry), "initrd") => {
+                    if entry.modules.len() >= MAX_MODULES {
+                        return Err(ConfigError::TooManyModules(line_number));
+                    }
+                    if value.len() > MAX_MODULE_NAME_LENGTH {
+                        return Err(ConfigError::ModulePathTooLong(line_number));
+                    }
+                    entry.modules.push(to_uefi_path(value))
+                }
+                _ => return Err(ConfigError::UnknownKey(line_number))
+            }
+        }
+
+        if config.entries.is_empty() {
+            return Err(ConfigError::NoEntries);
+        }
+        if config.default_entry >= config.entries.len() {
+            return Err(ConfigError::DefaultEntryOutOfRange(config.default_entry));
+        }
+        Ok(config)
+    }
+}
+
+fn parse_resolution(value: &str) -> Option<(u32, u32)> {
+    let mut size = value.splitn(2, 'x');
+    let width = size.next()?.trim().parse().ok()?;
+    let height = size.next()?.trim().parse().ok()?;
+    Some((width, height))
+}
+
+fn parse_number(value: &str) -> Option<u64> {
+    let value = value.replace('_', "");
+    if value.starts_with("0x") {
+        u64::from_str_radix(&value[2..], 16).ok()
+    } else {
+        value.parse().ok()
+    }
+}
+
+fn to_uefi_path(path: &str) -> String {
+    path.replace('/', "\\")
+}
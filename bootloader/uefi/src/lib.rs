@@ -4,35 +4,67 @@
 
 extern crate alloc;
 
-use core::mem;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::{mem, ptr, slice, str};
 
 use elf;
+use boot_protocol::{BOOT_PROTOCOL_VERSION, BootInfo, BootInfoHeader};
+use boot_protocol::command_line::CommandLine;
+use boot_protocol::kernel::{
+    KERNEL_HEAP_MAX_SIZE, KERNEL_HEAP_START, KernelSegment, KernelSegments, KernelStack,
+};
+use boot_protocol::memory::PageTableInfo;
+use boot_protocol::module::{Module, Modules};
 use elf::loader::ELF64Loader;
+use elf::ProgramHeaderFlags;
+use uefi_wrapper::Handle;
 use uefi_wrapper::boot_services::BootServices;
 use uefi_wrapper::memory::{AllocateType, MemoryType};
-use uefi_wrapper::println;
+use uefi_wrapper::{print, println};
 use uefi_wrapper::protocols::console::text_input::SimpleTextInputProtocol;
 use uefi_wrapper::protocols::console::text_output::SimpleTextOutputProtocol;
 use uefi_wrapper::runtime_services::{ResetType, RuntimeServices};
 use uefi_wrapper::system_table::SystemTable;
+use x86_64::address::align_up;
+use x86_64::instructions::switch_stack_and_call;
+use x86_64::paging::PAGE_SIZE_2MB;
+use x86_64::paging::page::PTEntryFlags;
 
-use crate::arch::paging::{map_page, PAGE_SIZE};
+use crate::arch::paging::{enable_no_execute, PAGE_SIZE, PageTables};
+use crate::config::{BootEntry, Config};
+use crate::memory::{
+    allocate_memory_regions, identity_map_bootloader, KERNEL_IMAGE_MEMORY_TYPE,
+    KERNEL_STACK_MEMORY_TYPE, max_physical_address, memory_regions, MODULE_MEMORY_TYPE,
+};
 use crate::protocol::file::read_file;
+use crate::protocol::graphics::{frame_buffer_info, set_graphics_mode};
+use crate::protocol::rng::random_u64;
 
 pub mod boot_menu;
+pub mod config;
+pub mod variable;
 mod arch;
+mod memory;
 mod protocol;
 
-const KERNEL_PATH: &str = "\\boot\\kernel";
+const MIN_PHYSICAL_MEMORY_SIZE: u64 = 0x1_0000_0000;
+const HIGHER_HALF_START: u64 = 0xffff_8000_0000_0000;
+const KERNEL_STACK_END: u64 = 0xffff_ffff_7000_0000;
+const KERNEL_SLIDE_RANGE: u64 = 0x4000_0000;
+const KERNEL_SLIDE_ALIGN: u64 = 0x20_0000;
 
+static mut IMAGE_HANDLE: Option<Handle> = None;
 static mut BOOT_SERVICES: Option<&BootServices> = None;
 static mut RUNTIME_SERVICES: Option<&'static RuntimeServices> = None;
 static mut CON_OUT: Option<&SimpleTextOutputProtocol> = None;
 static mut CON_IN: Option<&SimpleTextInputProtocol> = None;
 
-pub unsafe fn init(system_table: SystemTable) {
+pub unsafe fn init(image_handle: Handle, system_table: SystemTable) {
     uefi_wrapper::init(system_table);
 
+    IMAGE_HANDLE = Some(image_handle);
     BOOT_SERVICES = Some(uefi_wrapper::system_table().boot_services());
     RUNTIME_SERVICES = Some(uefi_wrapper::system_table().runtime_services());
     CON_OUT = Some(uefi_wrapper::system_table().con_out());
@@ -41,6 +73,12 @@ pub unsafe fn init(system_table: SystemTable) {
     con_out().clear_screen().unwrap();
 }
 
+pub fn image_handle() -> Handle {
+    unsafe {
+        IMAGE_HANDLE.unwrap()
+    }
+}
+
 pub fn boot_services<'a>() -> &'a BootServices {
     unsafe {
         BOOT_SERVICES.unwrap()
@@ -85,22 +123,50 @@ pub fn set_max_largest_screen_mode() {
 }
 
 
-pub fn boot_kernel() {
+pub fn boot_kernel(entry: &BootEntry, config: &Config) {
+    set_graphics_mode(config.resolution).expect("Could not set graphics mode");
     con_out().clear_screen().unwrap();
     println!("Booting kernel...");
 
+    unsafe { enable_no_execute(); }
+    let mut page_tables = PageTables::new();
+
     let kernel_entry_point;
+    let kernel_slide;
+    let mut kernel_segments = KernelSegments::new();
     {
-        let kernel_file = read_file(KERNEL_PATH)
-            .expect("Could not read file");
+        let kernel_file = read_file(&entry.kernel)
+            .expect("Could not read kernel file");
         println!("Kernel file size: {}B", kernel_file.len());
 
         let kernel_loader = ELF64Loader::new(kernel_file.as_slice())
             .expect("Could not create instance of ELF64Loader");
 
-        assert_eq!(kernel_loader.file_header().file_type(), elf::FileType::EXECUTABLE,
-                   "Kernel file is not executable");
+        let position_independent = kernel_loader.is_position_independent();
+        assert!(kernel_loader.file_header().file_type() == elf::FileType::EXECUTABLE
+                    || position_independent, "Kernel file is not executable");
+
+        match kernel_loader.build_id() {
+            Some(build_id) => {
+                print!("Kernel build-id: ");
+                for byte in build_id {
+                    print!("{:02x}", byte);
+                }
+                println!();
+            }
+            None => println!("Kernel build-id: none"),
+        }
+        let boot_protocol_version = kernel_loader.boot_protocol_version()
+            .expect("Kernel does not declare a boot protocol version");
+        assert!(boot_protocol_version == BOOT_PROTOCOL_VERSION,
+                "Kernel requires boot protocol version {}, but bootloader provides version {}; \
+                 refusing to boot",
+                boot_protocol_version, BOOT_PROTOCOL_VERSION);
+
+        kernel_slide = if position_independent { choose_kernel_slide() } else { 0 };
+        println!("Kernel slide: {:#x}", kernel_slide);
 
+        let mut loaded_segments = Vec::new();
         for program_header in kernel_loader.program_header_iter()
             .expect("Could not get program_header")
             .into_iter() {
@@ -108,34 +174,239 @@ pub fn boot_kernel() {
                 continue;
             }
 
-            let pages = program_header.segment_pages(PAGE_SIZE as u64) as usize;
+            let start_address = program_header.start_address() + kernel_slide;
+            assert!(start_address >= HIGHER_HALF_START,
+                    "Kernel segment {:#x} is not in the higher half", start_address);
+
+            // The slide is page aligned, so a segment keeps its offset into its first page.
+            let page_offset = (start_address % PAGE_SIZE as u64) as usize;
+            let page_start = start_address as usize - page_offset;
+            let flags = program_header.flags();
+            let pages = (page_offset + program_header.segment_memory_size() as usize
+                + PAGE_SIZE - 1) / PAGE_SIZE;
             let allocate_page =
                 boot_services().allocate_pages(
                     AllocateType::AnyPages,
-                    MemoryType::LoaderData,
+                    KERNEL_IMAGE_MEMORY_TYPE,
                     pages,
                 ).expect("Could not allocate pages");
 
             unsafe {
-                for i in 0..pages {
-                    map_page(
-                        program_header.start_address() as usize + i * PAGE_SIZE,
-                        allocate_page.0 as usize + i * PAGE_SIZE,
-                    );
-                }
+                ptr::write_bytes(allocate_page.0 as *mut u8, 0, pages * PAGE_SIZE);
+                kernel_loader.load_program_at(
+                    &program_header,
+                    allocate_page.0 as usize + page_offset,
+                );
+            }
+            for i in 0..pages {
+                page_tables.map_page(
+                    page_start + i * PAGE_SIZE,
+                    allocate_page.0 as usize + i * PAGE_SIZE,
+                    segment_page_flags(flags),
+                );
             }
+            loaded_segments.push((
+                program_header.start_address(),
+                program_header.segment_memory_size(),
+                allocate_page.0 + page_offset as u64,
+            ));
+            kernel_segments.push(KernelSegment {
+                start: page_start as u64,
+                length: (pages * PAGE_SIZE) as u64,
+                writable: flags.contains(ProgramHeaderFlags::WRITABLE),
+                executable: flags.contains(ProgramHeaderFlags::EXECUTABLE),
+            });
         }
 
-        unsafe { kernel_loader.load_programs().expect("Could not load kernel"); }
-        kernel_entry_point = kernel_loader.file_header().entry_point();
+        if position_independent {
+            unsafe {
+                kernel_loader.apply_relocations(kernel_slide, |address| {
+                    loaded_segments.iter()
+                        .find(|&&(start, size, _)| start <= address && address < start + size)
+                        .map(|&(start, _, physical)| (physical + address - start) as *mut u8)
+                }).expect("Could not relocate kernel");
+            }
+        }
+        kernel_entry_point = kernel_loader.file_header().entry_point() + kernel_slide;
     }
     println!("Kernel entry point: {:#x}", kernel_entry_point);
-    let kernel_entry_point: boot_protocol::KernelEntryFunction =
-        unsafe { mem::transmute(kernel_entry_point) };
 
-    kernel_entry_point(boot_protocol::BootInfo {
-        con_out: uefi_wrapper::system_table().con_out()
-    });
+    let modules = load_modules(&entry.modules);
+    for module in modules.iter() {
+        println!("Module {}: {}B at {:#x}", module.name(), module.size, module.physical_address);
+    }
+
+    let kernel_stack = allocate_kernel_stack(&mut page_tables, config.kernel_stack_size);
+    println!("Kernel stack: {:#x}-{:#x}", kernel_stack.start, kernel_stack.end);
+
+    let frame_buffer = frame_buffer_info().expect("Could not get frame buffer");
+    println!("Frame buffer: {}x{} at {:#x}", frame_buffer.width, frame_buffer.height, frame_buffer.base);
+
+    let physical_memory_size = align_up(
+        max_physical_address()
+            .max(MIN_PHYSICAL_MEMORY_SIZE)
+            .max(frame_buffer.base + frame_buffer.size as u64),
+        PAGE_SIZE_2MB,
+    );
+    let physical_memory_offset = config.physical_memory_offset;
+    check_virtual_layout(&kernel_segments, &kernel_stack, physical_memory_offset,
+                         physical_memory_size);
+    identity_map_bootloader(&mut page_tables);
+    page_tables.map_physical_memory(physical_memory_offset, physical_memory_size);
+    let page_table_info = PageTableInfo {
+        pml4_table_address: page_tables.pml4_table_address().as_u64(),
+        physical_memory_size,
+    };
+    println!("Page tables: PML4 at {:#x}, {:#x} bytes of physical memory mapped at {:#x}",
+             page_table_info.pml4_table_address, physical_memory_size, physical_memory_offset);
+
+    let boot_info_address = boot_services().allocate_pages(
+        AllocateType::AnyPages,
+        MemoryType::LOADER_DATA,
+        (mem::size_of::<BootInfo>() + PAGE_SIZE - 1) / PAGE_SIZE,
+    ).expect("Could not allocate pages for boot info").0;
+    let mut command_line = allocate_command_line(&entry.command_line);
+    println!("Kernel command line: {}", entry.command_line);
+    let memory_regions_buffer = allocate_memory_regions();
+    println!("Exiting boot services...");
+    let memory_map = boot_services().exit_boot_services(image_handle());
+    let mut memory_regions = memory_regions(memory_regions_buffer, &memory_map);
+    memory_regions.relocate(physical_memory_offset);
+    command_line.relocate(physical_memory_offset);
+
+    unsafe {
+        (boot_info_address as *mut BootInfo).write(BootInfo {
+            header: BootInfoHeader::new(),
+            memory_regions,
+            frame_buffer,
+            kernel_segments,
+            kernel_stack,
+            kernel_slide,
+            page_tables: page_table_info,
+            physical_memory_offset,
+            command_line,
+            modules,
+        });
+
+        page_tables.activate();
+        switch_stack_and_call(
+            kernel_stack.end,
+            kernel_entry_point,
+            boot_info_address + physical_memory_offset,
+        );
+    }
+}
+
+fn check_virtual_layout(
+    kernel_segments: &KernelSegments,
+    kernel_stack: &KernelStack,
+    physical_memory_offset: u64,
+    physical_memory_size: u64,
+) {
+    let physical_memory_end = physical_memory_offset.checked_add(physical_memory_size)
+        .expect("Physical memory mapping overflows the address space");
+    let mut ranges = vec![
+        ("physical memory mapping", physical_memory_offset, physical_memory_end),
+        ("kernel heap", KERNEL_HEAP_START, KERNEL_HEAP_START + KERNEL_HEAP_MAX_SIZE),
+        ("kernel stack", kernel_stack.guard_page_start, kernel_stack.end),
+    ];
+    for segment in kernel_segments.iter() {
+        ranges.push(("kernel segment", segment.start, segment.end()));
+    }
+    for (i, &(name, start, end)) in ranges.iter().enumerate() {
+        for &(other_name, other_start, other_end) in &ranges[i + 1..] {
+            assert!(end <= other_start || other_end <= start,
+                    "{} {:#x}-{:#x} overlaps {} {:#x}-{:#x}",
+                    name, start, end, other_name, other_start, other_end);
+        }
+    }
+}
+
+fn choose_kernel_slide() -> u64 {
+    match random_u64() {
+        Some(random) => random % (KERNEL_SLIDE_RANGE / KERNEL_SLIDE_ALIGN) * KERNEL_SLIDE_ALIGN,
+        None => {
+            println!("No entropy source, loading kernel at its linked address");
+            0
+        }
+    }
+}
+
+fn load_modules(paths: &[String]) -> Modules {
+    let mut modules = Modules::new();
+    for path in paths {
+        let file = match read_file(path) {
+            Ok(file) => file,
+            Err(error) => {
+                println!("Could not read module {}: {:?}, skipping it", path, error);
+                continue;
+            }
+        };
+        let address = boot_services().allocate_pages(
+            AllocateType::AnyPages,
+            MODULE_MEMORY_TYPE,
+            ((file.len() + PAGE_SIZE - 1) / PAGE_SIZE).max(1),
+        ).expect("Could not allocate pages for module").0;
+        unsafe {
+            slice::from_raw_parts_mut(address as *mut u8, file.len()).copy_from_slice(&file);
+        }
+        modules.push(Module::new(path, address, file.len() as u64));
+    }
+    modules
+}
+
+fn allocate_command_line(command_line: &str) -> CommandLine {
+    let address = boot_services().allocate_pages(
+        AllocateType::AnyPages,
+        MemoryType::LOADER_DATA,
+        command_line.len() / PAGE_SIZE + 1,
+    ).expect("Could not allocate pages for command line").0;
+    unsafe {
+        let buffer = slice::from_raw_parts_mut(address as *mut u8, command_line.len());
+        buffer.copy_from_slice(command_line.as_bytes());
+        CommandLine::new(str::from_utf8_unchecked(buffer))
+    }
+}
+
+fn allocate_kernel_stack(page_tables: &mut PageTables, size: u64) -> KernelStack {
+    assert!(size != 0 && size % PAGE_SIZE as u64 == 0,
+            "Kernel stack size {:#x} is not a non-zero multiple of the page size", size);
+    let guard_page_start = KERNEL_STACK_END.checked_sub(size + PAGE_SIZE as u64)
+        .filter(|&address| address >= HIGHER_HALF_START)
+        .expect("Kernel stack does not fit in the higher half");
+    let start = guard_page_start + PAGE_SIZE as u64;
+
+    let pages = size as usize / PAGE_SIZE;
+    let stack_address = boot_services().allocate_pages(
+        AllocateType::AnyPages,
+        KERNEL_STACK_MEMORY_TYPE,
+        pages,
+    ).expect("Could not allocate kernel stack").0;
+
+    for i in 0..pages {
+        page_tables.map_page(
+            start as usize + i * PAGE_SIZE,
+            stack_address as usize + i * PAGE_SIZE,
+            PTEntryFlags::PRESENT | PTEntryFlags::WRITABLE | PTEntryFlags::EXECUTE_DISABLE,
+        );
+    }
+
+    KernelStack {
+        start,
+        end: KERNEL_STACK_END,
+        guard_page_start,
+    }
+}
+
+fn segment_page_flags(flags: ProgramHeaderFlags) -> PTEntryFlags {
+    let mut page_flags = PTEntryFlags::PRESENT;
+    if flags.contains(ProgramHeaderFlags::WRITABLE) {
+        page_flags = page_flags | PTEntryFlags::WRITABLE;
+    }
+    if !flags.contains(ProgramHeaderFlags::EXECUTABLE) {
+        page_flags = page_flags | PTEntryFlags::EXECUTE_DISABLE;
+    }
+    page_flags
 }
 
 
@@ -4,29 +4,47 @@
 
 extern crate alloc;
 
-use alloc::vec;
+use alloc::string::ToString;
+use alloc::vec::Vec;
 
 use uefi::*;
 use uefi::boot_menu::*;
+use uefi::config::Config;
+use uefi::variable::{last_boot_entry, set_last_boot_entry};
+use uefi_wrapper::Handle;
 use uefi_wrapper::println;
 use uefi_wrapper::system_table::SystemTable;
 
 #[no_mangle]
-unsafe extern "efiapi" fn efi_main(_image_handle: usize, system_table: SystemTable) {
-    uefi::init(system_table);
+unsafe extern "efiapi" fn efi_main(image_handle: Handle, system_table: SystemTable) {
+    uefi::init(image_handle, system_table);
     println!("Welcome to MonorsOS UEFI-Bootloader v{}", env!("CARGO_PKG_VERSION"));
 
-    {
-        let mut boot_menu = BootMenu::new(
-            1,
-            vec![
-                BootMenuOption::new("Boot MonorsOS", || ()),
-                BootMenuOption::new("Shutdown", shutdown)
-            ]);
-        boot_menu.menu_loop();
-    }
-
-    boot_kernel();
+    let config = Config::load();
+    let default_entry = last_boot_entry(&config).unwrap_or(config.default_entry);
+    // A timeout of 0 boots the default entry without showing the menu.
+    let selected_entry = if config.timeout == 0 {
+        config.entries[default_entry].clone()
+    } else {
+        let mut options = config.entries.iter()
+            .map(|entry| {
+                BootMenuOption::new(&entry.name, || ()).with_command_line(&entry.command_line)
+            })
+            .collect::<Vec<_>>();
+        options.push(BootMenuOption::new("Shutdown", shutdown));
+        let mut boot_menu = BootMenu::new(config.timeout, default_entry, options);
+        let selected = boot_menu.menu_loop();
+        if let Err(error) = set_last_boot_entry(&config, selected) {
+            println!("Could not save last boot entry: {:?}", error);
+        }
+        let mut entry = config.entries[selected].clone();
+        if let Some(command_line) = boot_menu.option(selected).command_line() {
+            entry.command_line = command_line.to_string();
+        }
+        entry
+    };
+
+    boot_kernel(&selected_entry, &config);
     loop {}
 }
 
@@ -0,0 +1,110 @@
+use alloc::vec;
+use core::{mem, slice};
+
+use boot_protocol::memory::{MemoryRegion, MemoryRegionKind, MemoryRegions};
+use uefi_wrapper::memory::{
+    ALLOCATE_PAGE_SIZE, AllocateType, MemoryDescriptor, MemoryMap, MemoryType,
+};
+use x86_64::paging::page::PTEntryFlags;
+
+use crate::arch::paging::PageTables;
+use crate::boot_services;
+
+pub const KERNEL_IMAGE_MEMORY_TYPE: MemoryType = MemoryType::os_defined(0);
+pub const PAGE_TABLES_MEMORY_TYPE: MemoryType = MemoryType::os_defined(1);
+pub const KERNEL_STACK_MEMORY_TYPE: MemoryType = MemoryType::os_defined(2);
+pub const MODULE_MEMORY_TYPE: MemoryType = MemoryType::os_defined(3);
+
+fn memory_region_kind(memory_type: MemoryType) -> MemoryRegionKind {
+    match memory_type {
+        MemoryType::CONVENTIONAL_MEMORY => MemoryRegionKind::Usable,
+        MemoryType::BOOT_SERVICES_CODE
+        | MemoryType::BOOT_SERVICES_DATA => MemoryRegionKind::BootServicesReclaimable,
+        MemoryType::LOADER_CODE
+        | MemoryType::LOADER_DATA => MemoryRegionKind::BootloaderReclaimable,
+        MemoryType::RUNTIME_SERVICES_CODE
+        | MemoryType::RUNTIME_SERVICES_DATA => MemoryRegionKind::UefiRuntimeServices,
+        MemoryType::ACPI_RECLAIM_MEMORY => MemoryRegionKind::AcpiReclaimable,
+        MemoryType::ACPI_MEMORY_NVS => MemoryRegionKind::AcpiNvs,
+        MemoryType::MEMORY_MAPPED_IO
+        | MemoryType::MEMORY_MAPPED_IO_PORT_SPACE => MemoryRegionKind::Mmio,
+        KERNEL_IMAGE_MEMORY_TYPE => MemoryRegionKind::KernelImage,
+        PAGE_TABLES_MEMORY_TYPE => MemoryRegionKind::PageTables,
+        KERNEL_STACK_MEMORY_TYPE => MemoryRegionKind::KernelStack,
+        MODULE_MEMORY_TYPE => MemoryRegionKind::Module,
+        _ => MemoryRegionKind::Reserved
+    }
+}
+
+pub fn allocate_memory_regions() -> &'static mut [MemoryRegion] {
+    // Every allocation from here on may split a descriptor, so reserve room for more regions
+    // than the current memory map reports.
+    const SPARE_REGIONS: usize = 16;
+    let regions = boot_services().memory_map_size()
+        / mem::size_of::<MemoryDescriptor>() + SPARE_REGIONS;
+    let pages = (regions * mem::size_of::<MemoryRegion>() + ALLOCATE_PAGE_SIZE - 1)
+        / ALLOCATE_PAGE_SIZE;
+
+    let address = boot_services().allocate_pages(
+        AllocateType::AnyPages,
+        MemoryType::LOADER_DATA,
+        pages,
+    ).expect("Could not allocate pages for memory regions");
+    unsafe {
+        slice::from_raw_parts_mut(
+            address.0 as *mut MemoryRegion,
+            pages * ALLOCATE_PAGE_SIZE / mem::size_of::<MemoryRegion>(),
+        )
+    }
+}
+
+pub fn memory_regions(
+    buffer: &'static mut [MemoryRegion],
+    memory_map: &MemoryMap,
+) -> MemoryRegions {
+    let mut length = 0;
+    for descriptor in memory_map.iter() {
+        assert!(length < buffer.len(), "Too many memory regions");
+        buffer[length] = MemoryRegion {
+            start: descriptor.start_address().0,
+            length: descriptor.pages() * ALLOCATE_PAGE_SIZE as u64,
+            kind: memory_region_kind(descriptor.memory_type()),
+        };
+        length += 1;
+    }
+    MemoryRegions::from_regions(&mut buffer[..length])
+}
+
+pub fn max_physical_address() -> u64 {
+    let mut buffer = vec![0u8; boot_services().memory_map_size()];
+    let (memory_map, _) = boot_services().memory_map(&mut buffer)
+        .expect("Could not get memory map");
+    memory_map.iter()
+        .map(|descriptor| descriptor.start_address().0
+            + descriptor.pages() * ALLOCATE_PAGE_SIZE as u64)
+        .max()
+        .unwrap_or(0)
+}
+
+// Only the bootloader image and the stack it runs on are still used between switching to the
+// kernel's page tables and jumping to the kernel, so nothing else is identity mapped.
+pub fn identity_map_bootloader(page_tables: &mut PageTables) {
+    let stack_marker = 0u8;
+    let stack_address = &stack_marker as *const u8 as u64;
+    let mut buffer = vec![0u8; boot_services().memory_map_size()];
+    let (memory_map, _) = boot_services().memory_map(&mut buffer)
+        .expect("Could not get memory map");
+    for descriptor in memory_map.iter() {
+        let start = descriptor.start_address().0;
+        let length = descriptor.pages() * ALLOCATE_PAGE_SIZE as u64;
+        if descriptor.memory_type() == MemoryType::LOADER_CODE {
+            page_tables.identity_map(start, length, PTEntryFlags::PRESENT | PTEntryFlags::WRITABLE);
+        } else if start <= stack_address && stack_address < start + length {
+            page_tables.identity_map(
+                start,
+                length,
+                PTEntryFlags::PRESENT | PTEntryFlags::WRITABLE | PTEntryFlags::EXECUTE_DISABLE,
+            );
+        }
+    }
+}
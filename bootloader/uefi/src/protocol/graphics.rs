@@ -0,0 +1,79 @@
+use boot_protocol::frame_buffer::{FrameBufferInfo, PixelBitmask, PixelFormat};
+use uefi_wrapper::protocols::console::graphics_output::*;
+use uefi_wrapper::result::{Error, Result};
+
+use crate::boot_services;
+
+fn graphics_output<'a>() -> Result<&'a GraphicsOutputProtocol> {
+    boot_services().locate_protocol::<GraphicsOutputProtocol>(None)
+}
+
+fn has_frame_buffer(info: &GraphicsOutputModeInformation) -> bool {
+    match info.pixel_format() {
+        GraphicsPixelFormat::PixelRedGreenBlueReserved8BitPerColor
+        | GraphicsPixelFormat::PixelBlueGreenRedReserved8BitPerColor
+        | GraphicsPixelFormat::PixelBitMask => true,
+        _ => false
+    }
+}
+
+fn select_mode(graphics_output: &GraphicsOutputProtocol, resolution: (u32, u32)) -> Option<u32> {
+    let mut max_area = 0;
+    let mut largest_mode = None;
+    for i in 0..graphics_output.mode().max_mode() {
+        let info = match graphics_output.query_mode(i) {
+            Ok(info) => info,
+            Err(_) => continue
+        };
+        if !has_frame_buffer(&info) {
+            continue;
+        }
+
+        let mode_resolution = (info.horizontal_resolution(), info.vertical_resolution());
+        if mode_resolution == resolution {
+            return Some(i);
+        }
+        let area = mode_resolution.0 as u64 * mode_resolution.1 as u64;
+        if max_area < area {
+            max_area = area;
+            largest_mode = Some(i);
+        }
+    }
+    largest_mode
+}
+
+pub fn set_graphics_mode(resolution: (u32, u32)) -> Result {
+    let graphics_output = graphics_output()?;
+    match select_mode(graphics_output, resolution) {
+        Some(mode) if mode != graphics_output.mode().mode_number() => graphics_output.set_mode(mode),
+        Some(_) => Ok(()),
+        None => Err(Error::Unsupported)
+    }
+}
+
+pub fn frame_buffer_info() -> Result<FrameBufferInfo> {
+    let mode = graphics_output()?.mode();
+    let info = mode.info();
+    let pixel_format = match info.pixel_format() {
+        GraphicsPixelFormat::PixelRedGreenBlueReserved8BitPerColor => PixelFormat::Rgb,
+        GraphicsPixelFormat::PixelBlueGreenRedReserved8BitPerColor => PixelFormat::Bgr,
+        GraphicsPixelFormat::PixelBitMask => PixelFormat::Bitmask,
+        _ => return Err(Error::Unsupported)
+    };
+    let pixel_information = info.pixel_information();
+
+    Ok(FrameBufferInfo {
+        base: mode.frame_buffer_base().0,
+        size: mode.frame_buffer_size(),
+        width: info.horizontal_resolution() as usize,
+        height: info.vertical_resolution() as usize,
+        stride: info.pixels_per_scan_line() as usize,
+        pixel_format,
+        pixel_bitmask: PixelBitmask {
+            red: pixel_information.red_mask,
+            green: pixel_information.green_mask,
+            blue: pixel_information.blue_mask,
+            reserved: pixel_information.reserved_mask,
+        },
+    })
+}
@@ -1 +1,3 @@
 pub mod file;
+pub mod graphics;
+pub mod rng;
@@ -0,0 +1,11 @@
+use uefi_wrapper::protocols::rng::RngProtocol;
+use x86_64::instructions::rdrand;
+
+use crate::boot_services;
+
+pub fn random_u64() -> Option<u64> {
+    boot_services().locate_protocol::<RngProtocol>(None)
+        .and_then(|rng| rng.random_u64())
+        .ok()
+        .or_else(rdrand)
+}
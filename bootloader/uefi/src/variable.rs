@@ -0,0 +1,38 @@
+use uefi_wrapper::guid::GUID;
+use uefi_wrapper::result::Result;
+use uefi_wrapper::runtime_services::VariableAttributes;
+
+use crate::config::Config;
+use crate::runtime_services;
+
+pub const MONORS_VENDOR: GUID =
+    GUID::new((0x6f2a5c1e, 0x9d47, 0x4b83, [0xa1, 0x6e, 0x3c, 0x5d, 0x0b, 0x72, 0xe8, 0x94]));
+
+const LAST_BOOT_ENTRY: &str = "LastBootEntry";
+const LAST_BOOT_ENTRY_SIZE: usize = 256;
+
+pub fn last_boot_entry(config: &Config) -> Option<usize> {
+    let mut buffer = [0u8; LAST_BOOT_ENTRY_SIZE];
+    let (size, _) = runtime_services()
+        .get_variable(LAST_BOOT_ENTRY, &MONORS_VENDOR, &mut buffer)
+        .ok()?;
+    config.entries.iter().position(|entry| stored_name(&entry.name) == &buffer[..size])
+}
+
+// The variable lives in firmware flash, so it is only written when the entry changes.
+pub fn set_last_boot_entry(config: &Config, index: usize) -> Result {
+    if last_boot_entry(config) == Some(index) {
+        return Ok(());
+    }
+    runtime_services().set_variable(
+        LAST_BOOT_ENTRY,
+        &MONORS_VENDOR,
+        VariableAttributes::NON_VOLATILE | VariableAttributes::BOOTSERVICE_ACCESS,
+        stored_name(&config.entries[index].name),
+    )
+}
+
+fn stored_name(name: &str) -> &[u8] {
+    let name = name.as_bytes();
+    &name[..name.len().min(LAST_BOOT_ENTRY_SIZE)]
+}
@@ -1,16 +0,0 @@
-use core::alloc::{GlobalAlloc, Layout};
-
-#[global_allocator]
-static ALLOCATOR: Allocator = Allocator;
-
-pub struct Allocator;
-
-unsafe impl GlobalAlloc for Allocator {
-    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
-        unimplemented!()
-    }
-
-    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
-        unimplemented!()
-    }
-}
@@ -0,0 +1,163 @@
+use core::alloc::Layout;
+use core::mem;
+use core::ptr;
+
+use x86_64::address::{align_up, VirtualAddress};
+use x86_64::paging::PAGE_SIZE_4KB;
+use x86_64::paging::page::PTEntryFlags;
+
+use crate::memory::frame::{allocate_frame, free_frame, FrameSize};
+use crate::memory::paging::map_page;
+
+const MIN_BLOCK_SIZE: usize = mem::size_of::<FreeBlock>();
+
+struct FreeBlock {
+    size: usize,
+    next: *mut FreeBlock,
+}
+
+pub struct LinkedListHeap {
+    head: FreeBlock,
+    start: usize,
+    top: usize,
+    limit: usize,
+    grow_size: usize,
+}
+
+unsafe impl Send for LinkedListHeap {}
+
+impl LinkedListHeap {
+    pub const fn new(start: usize, max_size: usize, grow_size: usize) -> Self {
+        Self {
+            head: FreeBlock { size: 0, next: ptr::null_mut() },
+            start,
+            top: start,
+            limit: start + max_size,
+            grow_size,
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn size(&self) -> usize {
+        self.top - self.start
+    }
+
+    pub fn allocate(&mut self, layout: Layout) -> *mut u8 {
+        let (size, align) = Self::block_layout(layout);
+        unsafe {
+            if let Some(address) = self.allocate_from_list(size, align) {
+                return address as *mut u8;
+            }
+            self.grow(size + align);
+            if let Some(address) = self.allocate_from_list(size, align) {
+                return address as *mut u8;
+            }
+        }
+        ptr::null_mut()
+    }
+
+    pub unsafe fn deallocate(&mut self, pointer: *mut u8, layout: Layout) {
+        let (size, _) = Self::block_layout(layout);
+        self.add_free_region(pointer as usize, size);
+    }
+
+    pub fn grow(&mut self, size: usize) -> bool {
+        let size = align_up(size.max(self.grow_size) as u64, PAGE_SIZE_4KB) as usize;
+        if self.top + size > self.limit {
+            return false;
+        }
+
+        let mut mapped = 0;
+        for page in (self.top..self.top + size).step_by(PAGE_SIZE_4KB as usize) {
+            let frame = match allocate_frame(FrameSize::Size4KB) {
+                Some(frame) => frame,
+                None => break,
+            };
+            let flags = PTEntryFlags::PRESENT
+                | PTEntryFlags::WRITABLE
+                | PTEntryFlags::EXECUTE_DISABLE;
+            if unsafe { map_page(VirtualAddress::new(page as u64), frame, flags) }.is_err() {
+                free_frame(frame, FrameSize::Size4KB);
+                break;
+            }
+            mapped += PAGE_SIZE_4KB as usize;
+        }
+
+        // Keep whatever was mapped before a failure so that the next grow starts after it.
+        if mapped != 0 {
+            unsafe { self.add_free_region(self.top, mapped); }
+            self.top += mapped;
+        }
+        mapped == size
+    }
+
+    fn block_layout(layout: Layout) -> (usize, usize) {
+        let align = layout.align().max(mem::align_of::<FreeBlock>());
+        let size = align_up(layout.size().max(MIN_BLOCK_SIZE) as u64,
+                            mem::align_of::<FreeBlock>() as u64) as usize;
+        (size, align)
+    }
+
+    fn fit(block_start: usize, block_end: usize, size: usize, align: usize) -> Option<usize> {
+        let mut start = align_up(block_start as u64, align as u64) as usize;
+        if start != block_start && start - block_start < MIN_BLOCK_SIZE {
+            start = align_up((block_start + MIN_BLOCK_SIZE) as u64, align as u64) as usize;
+        }
+        let end = start.checked_add(size)?;
+        if end > block_end {
+            return None;
+        }
+        let excess = block_end - end;
+        if excess != 0 && excess < MIN_BLOCK_SIZE {
+            return None;
+        }
+        Some(start)
+    }
+
+    unsafe fn allocate_from_list(&mut self, size: usize, align: usize) -> Option<usize> {
+        let mut previous: *mut FreeBlock = &mut self.head;
+        while !(*previous).next.is_null() {
+            let block = (*previous).next;
+            let block_start = block as usize;
+            let block_end = block_start + (*block).size;
+            if let Some(start) = Self::fit(block_start, block_end, size, align) {
+                (*previous).next = (*block).next;
+                let end = start + size;
+                if start > block_start {
+                    self.add_free_region(block_start, start - block_start);
+                }
+                if block_end > end {
+                    self.add_free_region(end, block_end - end);
+                }
+                return Some(start);
+            }
+            previous = block;
+        }
+        None
+    }
+
+    unsafe fn add_free_region(&mut self, address: usize, size: usize) {
+        let head: *mut FreeBlock = &mut self.head;
+        let mut previous = head;
+        while !(*previous).next.is_null() && ((*previous).next as usize) < address {
+            previous = (*previous).next;
+        }
+
+        let next = (*previous).next;
+        let block = address as *mut FreeBlock;
+        block.write(FreeBlock { size, next });
+        (*previous).next = block;
+
+        if !next.is_null() && address + size == next as usize {
+            (*block).size += (*next).size;
+            (*block).next = (*next).next;
+        }
+        if previous != head && previous as usize + (*previous).size == address {
+            (*previous).size += (*block).size;
+            (*previous).next = (*block).next;
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use core::alloc::{GlobalAlloc, Layout};
+
+use boot_protocol::kernel::{KERNEL_HEAP_MAX_SIZE, KERNEL_HEAP_START};
+
+use crate::allocator::linked_list::LinkedListHeap;
+use crate::allocator::slab::{CacheStatistics, SIZE_CLASSES, SlabAllocator};
+use crate::sync::SpinLock;
+
+pub mod linked_list;
+pub mod slab;
+
+pub const HEAP_START: usize = KERNEL_HEAP_START as usize;
+pub const HEAP_MAX_SIZE: usize = KERNEL_HEAP_MAX_SIZE as usize;
+pub const HEAP_INITIAL_SIZE: usize = 0x10_0000;
+pub const HEAP_GROW_SIZE: usize = 0x1_0000;
+
+#[global_allocator]
+static ALLOCATOR: Allocator = Allocator;
+
+static HEAP: SpinLock<LinkedListHeap> =
+    SpinLock::new(LinkedListHeap::new(HEAP_START, HEAP_MAX_SIZE, HEAP_GROW_SIZE));
+static SLABS: SpinLock<SlabAllocator> = SpinLock::new(SlabAllocator::new());
+
+pub fn init() {
+    assert!(HEAP.lock().grow(HEAP_INITIAL_SIZE), "Could not initialize heap");
+}
+
+pub fn heap_size() -> usize {
+    HEAP.lock().size()
+}
+
+pub fn slab_statistics() -> [CacheStatistics; SIZE_CLASSES.len()] {
+    SLABS.lock().statistics()
+}
+
+pub struct Allocator;
+
+unsafe impl GlobalAlloc for Allocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let mut slabs = SLABS.lock();
+        match slabs.cache_mut(layout) {
+            Some(cache) => cache.allocate(&mut HEAP.lock()),
+            None => {
+                drop(slabs);
+                HEAP.lock().allocate(layout)
+            }
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let mut slabs = SLABS.lock();
+        match slabs.cache_mut(layout) {
+            Some(cache) => cache.deallocate(ptr),
+            None => {
+                drop(slabs);
+                HEAP.lock().deallocate(ptr, layout)
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use core::alloc::Layout;
+use core::mem;
+use core::ptr;
+
+use crate::allocator::linked_list::LinkedListHeap;
+
+pub const SLAB_SIZE: usize = 0x1000;
+pub const SIZE_CLASSES: [usize; 8] = [16, 32, 64, 128, 256, 512, 1024, 2048];
+
+const DEBUG: bool = cfg!(feature = "slab_debug");
+const FREE_POISON: u8 = 0x6b;
+const ALLOCATED_POISON: u8 = 0xa5;
+
+struct FreeObject {
+    next: *mut FreeObject,
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CacheStatistics {
+    pub object_size: usize,
+    pub slabs: usize,
+    pub total_objects: usize,
+    pub used_objects: usize,
+    pub allocations: u64,
+    pub frees: u64,
+}
+
+impl CacheStatistics {
+    pub fn free_objects(&self) -> usize {
+        self.total_objects - self.used_objects
+    }
+}
+
+pub struct SlabCache {
+    object_size: usize,
+    free_list: *mut FreeObject,
+    statistics: CacheStatistics,
+}
+
+unsafe impl Send for SlabCache {}
+
+impl SlabCache {
+    pub const fn new(object_size: usize) -> Self {
+        Self {
+            object_size,
+            free_list: ptr::null_mut(),
+            statistics: CacheStatistics {
+                object_size,
+                slabs: 0,
+                total_objects: 0,
+                used_objects: 0,
+                allocations: 0,
+                frees: 0,
+            },
+        }
+    }
+
+    pub fn object_size(&self) -> usize {
+        self.object_size
+    }
+
+    pub fn statistics(&self) -> CacheStatistics {
+        self.statistics
+    }
+
+    pub fn allocate(&mut self, heap: &mut LinkedListHeap) -> *mut u8 {
+        if self.free_list.is_null() && !self.grow(heap) {
+            return ptr::null_mut();
+        }
+
+        let object = self.free_list;
+        unsafe {
+            self.free_list = (*object).next;
+            if DEBUG {
+                self.check_poison(object);
+                ptr::write_bytes(object as *mut u8, ALLOCATED_POISON, self.object_size);
+            }
+        }
+        self.statistics.used_objects += 1;
+        self.statistics.allocations += 1;
+        object as *mut u8
+    }
+
+    pub unsafe fn deallocate(&mut self, pointer: *mut u8) {
+        let object = pointer as *mut FreeObject;
+        if DEBUG {
+            assert_eq!(pointer as usize % self.object_size, 0,
+                       "Slab object {:p} is not aligned to its {}-byte size class",
+                       pointer, self.object_size);
+            if self.is_poisoned(object) && self.contains_free(object) {
+                panic!("Double free of {}-byte slab object {:p}", self.object_size, pointer);
+            }
+            self.poison(object);
+        }
+        (*object).next = self.free_list;
+        self.free_list = object;
+        self.statistics.used_objects -= 1;
+        self.statistics.frees += 1;
+    }
+
+    fn grow(&mut self, heap: &mut LinkedListHeap) -> bool {
+        let layout = Layout::from_size_align(SLAB_SIZE, SLAB_SIZE).unwrap();
+        let slab = heap.allocate(layout);
+        if slab.is_null() {
+            return false;
+        }
+
+        let count = SLAB_SIZE / self.object_size;
+        for i in (0..count).rev() {
+            unsafe {
+                let object = slab.add(i * self.object_size) as *mut FreeObject;
+                if DEBUG {
+                    self.poison(object);
+                }
+                (*object).next = self.free_list;
+                self.free_list = object;
+            }
+        }
+        self.statistics.slabs += 1;
+        self.statistics.total_objects += count;
+        true
+    }
+
+    unsafe fn poison(&self, object: *mut FreeObject) {
+        let header = mem::size_of::<FreeObject>();
+        ptr::write_bytes((object as *mut u8).add(header), FREE_POISON, self.object_size - header);
+    }
+
+    unsafe fn is_poisoned(&self, object: *mut FreeObject) -> bool {
+        let header = mem::size_of::<FreeObject>();
+        let bytes = core::slice::from_raw_parts(
+            (object as *const u8).add(header), self.object_size - header);
+        bytes.iter().all(|&byte| byte == FREE_POISON)
+    }
+
+    unsafe fn check_poison(&self, object: *mut FreeObject) {
+        if !self.is_poisoned(object) {
+            panic!("{}-byte slab object {:p} was modified after free", self.object_size, object);
+        }
+    }
+
+    unsafe fn contains_free(&self, object: *mut FreeObject) -> bool {
+        let mut current = self.free_list;
+        while !current.is_null() {
+            if current == object {
+                return true;
+            }
+            current = (*current).next;
+        }
+        false
+    }
+}
+
+pub struct SlabAllocator {
+    caches: [SlabCache; SIZE_CLASSES.len()],
+}
+
+impl SlabAllocator {
+    pub const fn new() -> Self {
+        Self {
+            caches: [
+                SlabCache::new(SIZE_CLASSES[0]),
+                SlabCache::new(SIZE_CLASSES[1]),
+                SlabCache::new(SIZE_CLASSES[2]),
+                SlabCache::new(SIZE_CLASSES[3]),
+                SlabCache::new(SIZE_CLASSES[4]),
+                SlabCache::new(SIZE_CLASSES[5]),
+                SlabCache::new(SIZE_CLASSES[6]),
+                SlabCache::new(SIZE_CLASSES[7]),
+            ]
+        }
+    }
+
+    pub fn cache_mut(&mut self, layout: Layout) -> Option<&mut SlabCache> {
+        let size = layout.size().max(layout.align());
+        self.caches.iter_mut().find(|cache| cache.object_size >= size)
+    }
+
+    pub fn statistics(&self) -> [CacheStatistics; SIZE_CLASSES.len()] {
+        let mut statistics = [CacheStatistics::default(); SIZE_CLASSES.len()];
+        for (statistics, cache) in statistics.iter_mut().zip(self.caches.iter()) {
+            *statistics = cache.statistics();
+        }
+        statistics
+    }
+}
@@ -0,0 +1,15 @@
+pub use boot_protocol::command_line::{Arguments, ParsedCommandLine};
+
+static mut COMMAND_LINE: ParsedCommandLine<'static> = ParsedCommandLine::new("");
+
+pub fn init(command_line: &'static str) {
+    unsafe {
+        COMMAND_LINE = ParsedCommandLine::new(command_line);
+    }
+}
+
+pub fn command_line() -> ParsedCommandLine<'static> {
+    unsafe {
+        COMMAND_LINE
+    }
+}
@@ -0,0 +1,38 @@
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_MODE_512: u8 = 0x01;
+const PSF1_HEADER_SIZE: usize = 4;
+
+pub static DEFAULT_FONT: &[u8] = include_bytes!("font.psf");
+
+pub struct Font {
+    glyphs: &'static [u8],
+    glyph_count: usize,
+    height: usize,
+}
+
+impl Font {
+    pub const WIDTH: usize = 8;
+
+    pub fn parse(data: &'static [u8]) -> Option<Self> {
+        if data.len() < PSF1_HEADER_SIZE || data[..2] != PSF1_MAGIC {
+            return None;
+        }
+
+        let glyph_count = if data[2] & PSF1_MODE_512 != 0 { 512 } else { 256 };
+        let height = data[3] as usize;
+        let glyphs = data.get(PSF1_HEADER_SIZE..PSF1_HEADER_SIZE + glyph_count * height)?;
+        Some(Self { glyphs, glyph_count, height })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn glyph(&self, c: char) -> &[u8] {
+        let index = match c as usize {
+            index if index < self.glyph_count => index,
+            _ => '?' as usize
+        };
+        &self.glyphs[index * self.height..(index + 1) * self.height]
+    }
+}
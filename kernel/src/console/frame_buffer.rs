@@ -0,0 +1,109 @@
+use core::ptr;
+
+use boot_protocol::frame_buffer::{FrameBufferInfo, PixelBitmask, PixelFormat};
+use x86_64::address::PhysicalAddress;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+}
+
+pub struct FrameBuffer {
+    buffer: *mut u32,
+    width: usize,
+    height: usize,
+    stride: usize,
+    pixel_format: PixelFormat,
+    pixel_bitmask: PixelBitmask,
+}
+
+unsafe impl Send for FrameBuffer {}
+
+impl FrameBuffer {
+    pub unsafe fn new(info: &FrameBufferInfo, physical_memory_offset: u64) -> Self {
+        Self {
+            buffer: PhysicalAddress::new(info.base).to_virtual(physical_memory_offset).as_mut_ptr(),
+            width: info.width,
+            height: info.height,
+            stride: info.stride,
+            pixel_format: info.pixel_format,
+            pixel_bitmask: info.pixel_bitmask,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, color: Color) -> u32 {
+        match self.pixel_format {
+            PixelFormat::Rgb =>
+                color.red as u32 | (color.green as u32) << 8 | (color.blue as u32) << 16,
+            PixelFormat::Bgr =>
+                color.blue as u32 | (color.green as u32) << 8 | (color.red as u32) << 16,
+            PixelFormat::Bitmask => {
+                let bitmask = &self.pixel_bitmask;
+                FrameBuffer::scale(color.red, bitmask.red)
+                    | FrameBuffer::scale(color.green, bitmask.green)
+                    | FrameBuffer::scale(color.blue, bitmask.blue)
+            }
+        }
+    }
+
+    fn scale(value: u8, mask: u32) -> u32 {
+        if mask == 0 {
+            return 0;
+        }
+        let bits = mask.count_ones().min(8);
+        ((value as u32) >> (8 - bits)) << mask.trailing_zeros() & mask
+    }
+
+    pub fn write_pixel(&mut self, x: usize, y: usize, pixel: u32) {
+        if x < self.width && y < self.height {
+            unsafe { self.buffer.add(y * self.stride + x).write_volatile(pixel) }
+        }
+    }
+
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, pixel: u32) {
+        for y in y..(y + height).min(self.height) {
+            for x in x..(x + width).min(self.width) {
+                unsafe { self.buffer.add(y * self.stride + x).write_volatile(pixel) }
+            }
+        }
+    }
+
+    pub fn invert_rect(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        for y in y..(y + height).min(self.height) {
+            for x in x..(x + width).min(self.width) {
+                unsafe {
+                    let pixel = self.buffer.add(y * self.stride + x);
+                    pixel.write_volatile(!pixel.read_volatile());
+                }
+            }
+        }
+    }
+
+    pub fn scroll_up(&mut self, lines: usize, pixel: u32) {
+        let lines = lines.min(self.height);
+        unsafe {
+            ptr::copy(
+                self.buffer.add(lines * self.stride),
+                self.buffer,
+                (self.height - lines) * self.stride,
+            );
+        }
+        self.fill_rect(0, self.height - lines, self.width, lines, pixel);
+    }
+}
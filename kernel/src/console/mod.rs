@@ -0,0 +1,315 @@
+use core::fmt;
+
+use boot_protocol::frame_buffer::FrameBufferInfo;
+
+use crate::console::font::{DEFAULT_FONT, Font};
+use crate::console::frame_buffer::{Color, FrameBuffer};
+use crate::sync::SpinLock;
+
+pub mod font;
+pub mod frame_buffer;
+
+const TAB_WIDTH: usize = 8;
+const CURSOR_HEIGHT: usize = 2;
+const MAX_PARAMETERS: usize = 8;
+
+const PALETTE: [Color; 16] = [
+    Color::new(0x00, 0x00, 0x00),
+    Color::new(0xaa, 0x00, 0x00),
+    Color::new(0x00, 0xaa, 0x00),
+    Color::new(0xaa, 0x55, 0x00),
+    Color::new(0x00, 0x00, 0xaa),
+    Color::new(0xaa, 0x00, 0xaa),
+    Color::new(0x00, 0xaa, 0xaa),
+    Color::new(0xaa, 0xaa, 0xaa),
+    Color::new(0x55, 0x55, 0x55),
+    Color::new(0xff, 0x55, 0x55),
+    Color::new(0x55, 0xff, 0x55),
+    Color::new(0xff, 0xff, 0x55),
+    Color::new(0x55, 0x55, 0xff),
+    Color::new(0xff, 0x55, 0xff),
+    Color::new(0x55, 0xff, 0xff),
+    Color::new(0xff, 0xff, 0xff),
+];
+const DEFAULT_FOREGROUND: usize = 7;
+const DEFAULT_BACKGROUND: usize = 0;
+
+static CONSOLE: SpinLock<Option<Console>> = SpinLock::new(None);
+
+pub fn init(frame_buffer_info: &FrameBufferInfo, physical_memory_offset: u64) {
+    let frame_buffer = unsafe { FrameBuffer::new(frame_buffer_info, physical_memory_offset) };
+    let font = Font::parse(DEFAULT_FONT).expect("Could not parse font");
+    let mut console = Console::new(frame_buffer, font);
+    console.clear_screen();
+    *CONSOLE.lock() = Some(console);
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    if let Some(console) = CONSOLE.lock().as_mut() {
+        console.write_fmt(args).unwrap();
+    }
+}
+
+pub fn panic_print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    let mut console = match CONSOLE.try_lock() {
+        Some(console) => console,
+        None => unsafe {
+            // The panicking code may hold the lock and never release it.
+            CONSOLE.force_unlock();
+            CONSOLE.lock()
+        }
+    };
+    if let Some(console) = console.as_mut() {
+        let _ = console.write_fmt(args);
+    }
+}
+
+enum EscapeState {
+    Normal,
+    Escape,
+    ControlSequence,
+}
+
+pub struct Console {
+    frame_buffer: FrameBuffer,
+    font: Font,
+    columns: usize,
+    rows: usize,
+    column: usize,
+    row: usize,
+    foreground: usize,
+    background: usize,
+    cursor_visible: bool,
+    escape_state: EscapeState,
+    parameters: [usize; MAX_PARAMETERS],
+    parameter_count: usize,
+}
+
+impl Console {
+    pub fn new(frame_buffer: FrameBuffer, font: Font) -> Self {
+        let columns = frame_buffer.width() / Font::WIDTH;
+        let rows = frame_buffer.height() / font.height();
+        Self {
+            frame_buffer,
+            font,
+            columns,
+            rows,
+            column: 0,
+            row: 0,
+            foreground: DEFAULT_FOREGROUND,
+            background: DEFAULT_BACKGROUND,
+            cursor_visible: true,
+            escape_state: EscapeState::Normal,
+            parameters: [0; MAX_PARAMETERS],
+            parameter_count: 0,
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn clear_screen(&mut self) {
+        let background = self.background_pixel();
+        let (width, height) = (self.frame_buffer.width(), self.frame_buffer.height());
+        self.frame_buffer.fill_rect(0, 0, width, height, background);
+        self.column = 0;
+        self.row = 0;
+        self.toggle_cursor();
+    }
+
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        if self.cursor_visible != visible {
+            self.toggle_cursor();
+            self.cursor_visible = visible;
+            self.toggle_cursor();
+        }
+    }
+
+    fn foreground_pixel(&self) -> u32 {
+        self.frame_buffer.pixel(PALETTE[self.foreground])
+    }
+
+    fn background_pixel(&self) -> u32 {
+        self.frame_buffer.pixel(PALETTE[self.background])
+    }
+
+    fn toggle_cursor(&mut self) {
+        if self.cursor_visible {
+            let height = self.font.height();
+            self.frame_buffer.invert_rect(
+                self.column * Font::WIDTH,
+                (self.row + 1) * height - CURSOR_HEIGHT,
+                Font::WIDTH,
+                CURSOR_HEIGHT,
+            );
+        }
+    }
+
+    fn write_char(&mut self, c: char) {
+        match self.escape_state {
+            EscapeState::Normal => self.write_normal_char(c),
+            EscapeState::Escape => {
+                if c == '[' {
+                    self.parameters = [0; MAX_PARAMETERS];
+                    self.parameter_count = 0;
+                    self.escape_state = EscapeState::ControlSequence;
+                } else {
+                    self.escape_state = EscapeState::Normal;
+                }
+            }
+            EscapeState::ControlSequence => self.write_control_sequence_char(c),
+        }
+    }
+
+    fn write_normal_char(&mut self, c: char) {
+        match c {
+            '\x1b' => self.escape_state = EscapeState::Escape,
+            '\n' => self.move_cursor(0, self.row + 1),
+            '\r' => self.move_cursor(0, self.row),
+            '\t' => self.move_cursor((self.column / TAB_WIDTH + 1) * TAB_WIDTH, self.row),
+            '\x08' => self.move_cursor(self.column.saturating_sub(1), self.row),
+            c => {
+                self.toggle_cursor();
+                self.draw_glyph(c);
+                self.column += 1;
+                self.toggle_cursor();
+                if self.column >= self.columns {
+                    self.move_cursor(0, self.row + 1);
+                }
+            }
+        }
+    }
+
+    fn write_control_sequence_char(&mut self, c: char) {
+        match c {
+            '0'..='9' => {
+                if self.parameter_count == 0 {
+                    self.parameter_count = 1;
+                }
+                if let Some(parameter) = self.parameters.get_mut(self.parameter_count - 1) {
+                    *parameter = parameter.saturating_mul(10)
+                        .saturating_add(c as usize - '0' as usize);
+                }
+            }
+            ';' => self.parameter_count += 1,
+            '\x40'..='\x7e' => {
+                self.escape_state = EscapeState::Normal;
+                self.execute_control_sequence(c);
+            }
+            _ => self.escape_state = EscapeState::Normal
+        }
+    }
+
+    fn parameter(&self, index: usize, default: usize) -> usize {
+        match self.parameters.get(index) {
+            Some(&parameter) if index < self.parameter_count && parameter != 0 => parameter,
+            _ => default
+        }
+    }
+
+    fn execute_control_sequence(&mut self, command: char) {
+        match command {
+            'A' => self.move_cursor(self.column, self.row.saturating_sub(self.parameter(0, 1))),
+            'B' => self.move_cursor(self.column, (self.row + self.parameter(0, 1)).min(self.rows - 1)),
+            'C' => self.move_cursor((self.column + self.parameter(0, 1)).min(self.columns - 1), self.row),
+            'D' => self.move_cursor(self.column.saturating_sub(self.parameter(0, 1)), self.row),
+            'H' | 'f' => self.move_cursor(
+                (self.parameter(1, 1) - 1).min(self.columns - 1),
+                (self.parameter(0, 1) - 1).min(self.rows - 1),
+            ),
+            'J' => {
+                if self.parameter(0, 0) == 2 {
+                    let (column, row) = (self.column, self.row);
+                    self.clear_screen();
+                    self.move_cursor(column, row);
+                }
+            }
+            'K' => {
+                self.toggle_cursor();
+                let background = self.background_pixel();
+                let height = self.font.height();
+                self.frame_buffer.fill_rect(
+                    self.column * Font::WIDTH,
+                    self.row * height,
+                    (self.columns - self.column) * Font::WIDTH,
+                    height,
+                    background,
+                );
+                self.toggle_cursor();
+            }
+            'm' => self.select_graphic_rendition(),
+            'h' | 'l' => {
+                if self.parameter(0, 0) == 25 {
+                    self.set_cursor_visible(command == 'h');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn select_graphic_rendition(&mut self) {
+        if self.parameter_count == 0 {
+            self.parameter_count = 1;
+        }
+        for i in 0..self.parameter_count.min(MAX_PARAMETERS) {
+            match self.parameters[i] {
+                0 => {
+                    self.foreground = DEFAULT_FOREGROUND;
+                    self.background = DEFAULT_BACKGROUND;
+                }
+                1 => self.foreground |= 8,
+                22 => self.foreground &= 7,
+                parameter @ 30..=37 => self.foreground = parameter - 30,
+                39 => self.foreground = DEFAULT_FOREGROUND,
+                parameter @ 40..=47 => self.background = parameter - 40,
+                49 => self.background = DEFAULT_BACKGROUND,
+                parameter @ 90..=97 => self.foreground = parameter - 90 + 8,
+                parameter @ 100..=107 => self.background = parameter - 100 + 8,
+                _ => {}
+            }
+        }
+    }
+
+    fn move_cursor(&mut self, column: usize, row: usize) {
+        self.toggle_cursor();
+        self.column = column.min(self.columns - 1);
+        if row >= self.rows {
+            let background = self.background_pixel();
+            self.frame_buffer.scroll_up((row - self.rows + 1) * self.font.height(), background);
+            self.row = self.rows - 1;
+        } else {
+            self.row = row;
+        }
+        self.toggle_cursor();
+    }
+
+    fn draw_glyph(&mut self, c: char) {
+        let foreground = self.foreground_pixel();
+        let background = self.background_pixel();
+        let x = self.column * Font::WIDTH;
+        let y = self.row * self.font.height();
+        for (dy, line) in self.font.glyph(c).iter().enumerate() {
+            for dx in 0..Font::WIDTH {
+                let pixel = if line & (0x80 >> dx) != 0 { foreground } else { background };
+                self.frame_buffer.write_pixel(x + dx, y + dy, pixel);
+            }
+        }
+    }
+}
+
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c);
+        }
+        Ok(())
+    }
+}
@@ -3,9 +3,46 @@
 
 extern crate alloc;
 
+use boot_protocol::{BOOT_PROTOCOL_VERSION, BootInfo};
+use boot_protocol::note::BootProtocolVersionNote;
+
 pub mod allocator;
+pub mod command_line;
+pub mod console;
+pub mod memory;
+pub mod serial;
+pub mod sync;
+
+#[used]
+#[link_section = ".note.monors"]
+static BOOT_PROTOCOL_VERSION_NOTE: BootProtocolVersionNote =
+    BootProtocolVersionNote::new(BOOT_PROTOCOL_VERSION);
+
+#[macro_export]
+macro_rules! kprint {
+    ($($arg:tt)*) => {$crate::console::_print(format_args!($($arg)*))}
+}
 
-pub fn init() {}
+#[macro_export]
+macro_rules! kprintln {
+    () => {$crate::kprint!("\n")};
+    ($($arg:tt)*) => {$crate::kprint!("{}\n", format_args!($($arg)*))};
+}
+
+pub fn init(boot_info: &'static BootInfo) {
+    // The console is set up from the boot info, so a header mismatch can only be reported on the
+    // serial port.
+    serial::init();
+    assert!(boot_info.header.is_compatible(), "Incompatible boot info: {:?}", boot_info.header);
+    console::init(&boot_info.frame_buffer, boot_info.physical_memory_offset);
+    // The bootloader relocated the command line into the physical memory mapping, which the
+    // kernel keeps for its whole lifetime.
+    command_line::init(unsafe { boot_info.command_line.as_str() });
+    assert!(boot_info.kernel_segments.is_write_xor_execute(),
+            "Kernel image has writable and executable segments");
+    memory::init(&boot_info.memory_regions, boot_info.physical_memory_offset);
+    allocator::init();
+}
 
 #[alloc_error_handler]
 fn alloc_error_handler(layout: alloc::alloc::Layout) -> ! {
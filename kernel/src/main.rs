@@ -1,16 +1,54 @@
 #![no_std]
 #![no_main]
 
-use core::fmt::Write;
+use kernel::{allocator, console, kprintln, serial};
+use kernel::command_line::command_line;
+use kernel::memory::frame;
 
 #[no_mangle]
-pub extern "sysv64" fn _start(boot_info: boot_protocol::BootInfo) {
-    kernel::init();
-    boot_info.con_out.write_str("Hello, kernel").unwrap();
+pub extern "sysv64" fn _start(boot_info: &'static boot_protocol::BootInfo) -> ! {
+    kernel::init(boot_info);
+    kprintln!("Hello, kernel");
+    kprintln!("Boot protocol: version {}", boot_info.header.version);
+    kprintln!("Command line: {}", command_line().as_str());
+    for module in boot_info.modules.iter() {
+        kprintln!("Module {}: {} bytes at {:#x}",
+                  module.name(), module.size, module.physical_address);
+    }
+
+    kprintln!("Page tables: PML4 at {:#x}, physical memory at {:#x}",
+              boot_info.page_tables.pml4_table_address, boot_info.physical_memory_offset);
+    kprintln!("Kernel stack: {:#x}-{:#x}, guard page at {:#x}",
+              boot_info.kernel_stack.start,
+              boot_info.kernel_stack.end,
+              boot_info.kernel_stack.guard_page_start);
+    kprintln!("Kernel slide: {:#x}", boot_info.kernel_slide);
+    for segment in boot_info.kernel_segments.iter() {
+        kprintln!("Kernel segment: {:#x}-{:#x} {}{}",
+                  segment.start,
+                  segment.end(),
+                  if segment.writable { "W" } else { "-" },
+                  if segment.executable { "X" } else { "-" });
+    }
+
+    let statistics = frame::statistics();
+    kprintln!("Physical memory: {} KiB free, {} KiB used, {} KiB total",
+              statistics.free_bytes() / 1024,
+              statistics.used_bytes() / 1024,
+              statistics.total_bytes() / 1024);
+    kprintln!("Kernel heap: {} KiB mapped at {:#x}",
+              allocator::heap_size() / 1024, allocator::HEAP_START);
+    if command_line().get_bool("debug").unwrap_or(false) {
+        for statistics in allocator::slab_statistics().iter() {
+            kprintln!("{:?}", statistics);
+        }
+    }
     loop {}
 }
 
 #[panic_handler]
-fn panic(_info: &core::panic::PanicInfo) -> ! {
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    serial::print(format_args!("{}\n", info));
+    console::panic_print(format_args!("\x1b[91m{}\x1b[0m\n", info));
     loop {}
 }
@@ -0,0 +1,220 @@
+use core::slice;
+
+use boot_protocol::memory::{MemoryRegion, MemoryRegionKind};
+use x86_64::address::{align_down, align_up, PhysicalAddress};
+use x86_64::paging::{PAGE_SIZE_1GB, PAGE_SIZE_2MB, PAGE_SIZE_4KB};
+
+use crate::memory::physical_memory_offset;
+use crate::sync::SpinLock;
+
+const BITS_PER_WORD: usize = 64;
+
+static FRAME_ALLOCATOR: SpinLock<Option<BitmapFrameAllocator>> = SpinLock::new(None);
+
+pub fn init(memory_regions: &[MemoryRegion]) {
+    *FRAME_ALLOCATOR.lock() = Some(unsafe { BitmapFrameAllocator::new(memory_regions) });
+}
+
+pub fn allocate_frame(size: FrameSize) -> Option<PhysicalAddress> {
+    FRAME_ALLOCATOR.lock()
+        .as_mut()
+        .expect("Frame allocator is not initialized")
+        .allocate(size)
+}
+
+pub fn free_frame(address: PhysicalAddress, size: FrameSize) {
+    FRAME_ALLOCATOR.lock()
+        .as_mut()
+        .expect("Frame allocator is not initialized")
+        .free(address, size)
+}
+
+pub fn statistics() -> FrameStatistics {
+    FRAME_ALLOCATOR.lock()
+        .as_ref()
+        .expect("Frame allocator is not initialized")
+        .statistics()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FrameSize {
+    Size4KB,
+    Size2MB,
+    Size1GB,
+}
+
+impl FrameSize {
+    pub fn bytes(self) -> u64 {
+        match self {
+            FrameSize::Size4KB => PAGE_SIZE_4KB,
+            FrameSize::Size2MB => PAGE_SIZE_2MB,
+            FrameSize::Size1GB => PAGE_SIZE_1GB,
+        }
+    }
+
+    fn frames(self) -> usize {
+        (self.bytes() / PAGE_SIZE_4KB) as usize
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FrameStatistics {
+    pub total_frames: usize,
+    pub free_frames: usize,
+}
+
+impl FrameStatistics {
+    pub fn used_frames(&self) -> usize {
+        self.total_frames - self.free_frames
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_frames as u64 * PAGE_SIZE_4KB
+    }
+
+    pub fn free_bytes(&self) -> u64 {
+        self.free_frames as u64 * PAGE_SIZE_4KB
+    }
+
+    pub fn used_bytes(&self) -> u64 {
+        self.used_frames() as u64 * PAGE_SIZE_4KB
+    }
+}
+
+pub struct BitmapFrameAllocator {
+    bitmap: &'static mut [u64],
+    frames: usize,
+    total_frames: usize,
+    free_frames: usize,
+    next_word: usize,
+}
+
+impl BitmapFrameAllocator {
+    pub unsafe fn new(memory_regions: &[MemoryRegion]) -> Self {
+        let usable_regions = || memory_regions.iter()
+            .filter(|region| region.kind == MemoryRegionKind::Usable);
+
+        // Boot services memory still holds the firmware's GDT and IDT, and bootloader memory
+        // holds the boot info the kernel keeps referring to, so neither is ever handed out.
+        let memory_end = usable_regions()
+            .map(|region| align_down(region.end(), PAGE_SIZE_4KB))
+            .max()
+            .expect("No usable memory");
+        let frames = (memory_end / PAGE_SIZE_4KB) as usize;
+        let words = (frames + BITS_PER_WORD - 1) / BITS_PER_WORD;
+        let bitmap_size = align_up((words * 8) as u64, PAGE_SIZE_4KB);
+
+        let bitmap_start = usable_regions()
+            .map(|region| {
+                (align_up(region.start, PAGE_SIZE_4KB), align_down(region.end(), PAGE_SIZE_4KB))
+            })
+            .find(|&(start, end)| start != 0 && start + bitmap_size <= end)
+            .map(|(start, _)| start)
+            .expect("Could not find memory for frame bitmap");
+        let bitmap_address = PhysicalAddress::new(bitmap_start).to_virtual(physical_memory_offset());
+        let bitmap = slice::from_raw_parts_mut(bitmap_address.as_mut_ptr::<u64>(), words);
+        for word in bitmap.iter_mut() {
+            *word = !0;
+        }
+
+        let mut allocator = Self {
+            bitmap,
+            frames,
+            total_frames: 0,
+            free_frames: 0,
+            next_word: 0,
+        };
+        allocator.reclaim(memory_regions, MemoryRegionKind::Usable);
+
+        let bitmap_frames = (bitmap_size / PAGE_SIZE_4KB) as usize;
+        allocator.mark_range((bitmap_start / PAGE_SIZE_4KB) as usize, bitmap_frames, true);
+        allocator.free_frames -= bitmap_frames;
+        allocator
+    }
+
+    unsafe fn reclaim(&mut self, memory_regions: &[MemoryRegion], kind: MemoryRegionKind) {
+        for region in memory_regions.iter().filter(|region| region.kind == kind) {
+            // Frame 0 is never handed out so that a null physical address stays invalid.
+            let start = align_up(region.start, PAGE_SIZE_4KB).max(PAGE_SIZE_4KB);
+            let start = (start / PAGE_SIZE_4KB) as usize;
+            let end = (align_down(region.end(), PAGE_SIZE_4KB) / PAGE_SIZE_4KB) as usize;
+            let end = end.min(self.frames);
+            if start < end {
+                self.mark_range(start, end - start, false);
+                self.total_frames += end - start;
+                self.free_frames += end - start;
+            }
+        }
+    }
+
+    pub fn allocate(&mut self, size: FrameSize) -> Option<PhysicalAddress> {
+        let count = size.frames();
+        let index = match size {
+            FrameSize::Size4KB => self.find_free_frame()?,
+            _ => self.find_free_aligned_frames(count)?
+        };
+        self.mark_range(index, count, true);
+        self.free_frames -= count;
+        Some(PhysicalAddress::new(index as u64 * PAGE_SIZE_4KB))
+    }
+
+    pub fn free(&mut self, address: PhysicalAddress, size: FrameSize) {
+        assert!(address.is_aligned(size.bytes()), "Frame is not aligned: {:#x}", address.as_u64());
+        let count = size.frames();
+        let index = (address.as_u64() / PAGE_SIZE_4KB) as usize;
+        assert!(index + count <= self.frames, "Frame is out of range: {:#x}", address.as_u64());
+        assert!(self.is_range(index, count, true),
+                "Frame was already free: {:#x}", address.as_u64());
+
+        self.mark_range(index, count, false);
+        self.free_frames += count;
+    }
+
+    pub fn statistics(&self) -> FrameStatistics {
+        FrameStatistics {
+            total_frames: self.total_frames,
+            free_frames: self.free_frames,
+        }
+    }
+
+    fn find_free_frame(&mut self) -> Option<usize> {
+        let words = self.bitmap.len();
+        for i in 0..words {
+            let word_index = (self.next_word + i) % words;
+            let word = self.bitmap[word_index];
+            if word != !0 {
+                let index = word_index * BITS_PER_WORD + (!word).trailing_zeros() as usize;
+                if index < self.frames {
+                    self.next_word = word_index;
+                    return Some(index);
+                }
+            }
+        }
+        None
+    }
+
+    fn find_free_aligned_frames(&self, count: usize) -> Option<usize> {
+        let words = count / BITS_PER_WORD;
+        self.bitmap.chunks_exact(words)
+            .position(|chunk| chunk.iter().all(|&word| word == 0))
+            .map(|chunk_index| chunk_index * count)
+            .filter(|&index| index + count <= self.frames)
+    }
+
+    fn is_range(&self, index: usize, count: usize, used: bool) -> bool {
+        (index..index + count).all(|i| {
+            (self.bitmap[i / BITS_PER_WORD] >> (i % BITS_PER_WORD) & 1 == 1) == used
+        })
+    }
+
+    fn mark_range(&mut self, index: usize, count: usize, used: bool) {
+        for i in index..index + count {
+            let bit = 1 << (i % BITS_PER_WORD);
+            if used {
+                self.bitmap[i / BITS_PER_WORD] |= bit;
+            } else {
+                self.bitmap[i / BITS_PER_WORD] &= !bit;
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use boot_protocol::memory::MemoryRegions;
+
+pub mod frame;
+pub mod paging;
+
+static mut PHYSICAL_MEMORY_OFFSET: u64 = 0;
+
+pub fn init(memory_regions: &MemoryRegions, physical_memory_offset: u64) {
+    unsafe {
+        PHYSICAL_MEMORY_OFFSET = physical_memory_offset;
+        // The bootloader identity maps itself only to reach the kernel entry point.
+        paging::unmap_lower_half();
+    }
+    frame::init(memory_regions);
+}
+
+pub fn physical_memory_offset() -> u64 {
+    unsafe { PHYSICAL_MEMORY_OFFSET }
+}
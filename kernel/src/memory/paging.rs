@@ -0,0 +1,27 @@
+use x86_64::address::{PhysicalAddress, VirtualAddress};
+use x86_64::paging::mapper::{FrameAllocator, Mapper, MapperError};
+use x86_64::paging::page::PTEntryFlags;
+
+use crate::memory::frame::{allocate_frame, FrameSize};
+use crate::memory::physical_memory_offset;
+
+pub struct KernelFrameAllocator;
+
+impl FrameAllocator for KernelFrameAllocator {
+    fn allocate_frame(&mut self) -> Option<PhysicalAddress> {
+        allocate_frame(FrameSize::Size4KB)
+    }
+}
+
+pub unsafe fn map_page(virtual_address: VirtualAddress, frame: PhysicalAddress, flags: PTEntryFlags)
+                       -> Result<(), MapperError> {
+    Mapper::current(physical_memory_offset()).map(virtual_address, frame, flags, &mut KernelFrameAllocator)
+}
+
+pub unsafe fn unmap_lower_half() {
+    Mapper::current(physical_memory_offset()).unmap_lower_half()
+}
+
+pub fn translate(address: VirtualAddress) -> Option<PhysicalAddress> {
+    unsafe { Mapper::current(physical_memory_offset()) }.translate(address)
+}
@@ -0,0 +1,59 @@
+use core::fmt;
+use core::hint::spin_loop;
+
+use x86_64::instructions::{inb, outb};
+
+const COM1: u16 = 0x3f8;
+const LINE_STATUS_TRANSMIT_EMPTY: u8 = 1 << 5;
+
+pub fn init() {
+    SerialPort::new(COM1).init();
+}
+
+pub fn print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    let _ = SerialPort::new(COM1).write_fmt(args);
+}
+
+pub struct SerialPort {
+    port: u16,
+}
+
+impl SerialPort {
+    pub const fn new(port: u16) -> Self {
+        Self { port }
+    }
+
+    pub fn init(&self) {
+        unsafe {
+            outb(self.port + 1, 0x00);
+            outb(self.port + 3, 0x80);
+            outb(self.port, 0x03);
+            outb(self.port + 1, 0x00);
+            outb(self.port + 3, 0x03);
+            outb(self.port + 2, 0xc7);
+            outb(self.port + 4, 0x0b);
+        }
+    }
+
+    fn write_byte(&self, byte: u8) {
+        unsafe {
+            while inb(self.port + 5) & LINE_STATUS_TRANSMIT_EMPTY == 0 {
+                spin_loop();
+            }
+            outb(self.port, byte);
+        }
+    }
+}
+
+impl fmt::Write for SerialPort {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            if byte == b'\n' {
+                self.write_byte(b'\r');
+            }
+            self.write_byte(byte);
+        }
+        Ok(())
+    }
+}
@@ -59,6 +59,10 @@ impl PhysicalAddress {
     pub fn align_down(&mut self, align: u64) {
         self.0 = align_down(self.0, align);
     }
+
+    pub fn to_virtual(self, physical_memory_offset: u64) -> VirtualAddress {
+        VirtualAddress::new(self.0 + physical_memory_offset)
+    }
 }
 
 #[repr(transparent)]
@@ -118,6 +122,10 @@ impl VirtualAddress {
         self.0 = align_down(self.0, align);
     }
 
+    pub fn to_physical(self, physical_memory_offset: u64) -> PhysicalAddress {
+        PhysicalAddress::new(self.0 - physical_memory_offset)
+    }
+
     pub fn pml4_table_index(self) -> usize {
         (self.0 >> 39 & 0x1ff) as usize
     }
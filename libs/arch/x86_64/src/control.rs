@@ -1,5 +1,5 @@
 use crate::address::PhysicalAddress;
-use crate::instructions::{read_cr0, read_cr3, write_cr0, write_cr3};
+use crate::instructions::{rdmsr, read_cr0, read_cr3, write_cr0, write_cr3, wrmsr};
 
 pub struct CR0(u64);
 
@@ -46,3 +46,31 @@ impl CR3 {
         self.write();
     }
 }
+
+pub struct EFER(u64);
+
+impl EFER {
+    const MSR: u32 = 0xc000_0080;
+    const NO_EXECUTE_ENABLE: u64 = 1 << 11;
+
+    pub fn read() -> Self {
+        Self(unsafe { rdmsr(Self::MSR) })
+    }
+
+    unsafe fn write(&self) {
+        wrmsr(Self::MSR, self.0);
+    }
+
+    pub fn no_execute_enable(&self) -> bool {
+        self.0 & Self::NO_EXECUTE_ENABLE != 0
+    }
+
+    pub unsafe fn set_no_execute_enable(&mut self, enable: bool) {
+        if enable {
+            self.0 |= Self::NO_EXECUTE_ENABLE;
+        } else {
+            self.0 &= !Self::NO_EXECUTE_ENABLE;
+        }
+        self.write();
+    }
+}
@@ -26,6 +26,70 @@ pub unsafe fn write_cr3(cr3: u64) {
     asm!("mov cr3, {}", in(reg) cr3);
 }
 
+#[inline]
+pub unsafe fn rdmsr(msr: u32) -> u64 {
+    let (low, high): (u32, u32);
+    asm!("rdmsr", in("ecx") msr, out("eax") low, out("edx") high, options(nomem, nostack));
+    (high as u64) << 32 | low as u64
+}
+
+#[inline]
+pub unsafe fn wrmsr(msr: u32, value: u64) {
+    asm!("wrmsr", in("ecx") msr, in("eax") value as u32, in("edx") (value >> 32) as u32,
+         options(nostack));
+}
+
+#[inline]
+pub unsafe fn invlpg(address: u64) {
+    asm!("invlpg [{}]", in(reg) address, options(nostack));
+}
+
+#[inline]
+pub unsafe fn inb(port: u16) -> u8 {
+    let value;
+    asm!("in al, dx", in("dx") port, out("al") value, options(nomem, nostack));
+    value
+}
+
+#[inline]
+pub unsafe fn outb(port: u16, value: u8) {
+    asm!("out dx, al", in("dx") port, in("al") value, options(nomem, nostack));
+}
+
+pub unsafe fn switch_stack_and_call(stack_top: u64, function: u64, argument: u64) -> ! {
+    asm!(
+        "mov rsp, {}",
+        "xor rbp, rbp",
+        "call {}",
+        "2:",
+        "hlt",
+        "jmp 2b",
+        in(reg) stack_top,
+        in(reg) function,
+        in("rdi") argument,
+        options(noreturn),
+    );
+}
+
+pub fn rdrand() -> Option<u64> {
+    const RETRIES: usize = 10;
+    if unsafe { core::arch::x86_64::__cpuid(1).ecx } & (1 << 30) == 0 {
+        return None;
+    }
+    for _ in 0..RETRIES {
+        let value: u64;
+        let success: u8;
+        unsafe {
+            asm!("rdrand {}", "setc {}", out(reg) value, out(reg_byte) success,
+                 options(nomem, nostack));
+        }
+        if success != 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
 #[inline]
 pub fn halt() {
     unsafe {
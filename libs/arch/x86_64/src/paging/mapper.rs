@@ -0,0 +1,359 @@
+use core::ptr;
+
+use crate::address::{align_down, PhysicalAddress, VirtualAddress};
+use crate::control::CR3;
+use crate::instructions::{invlpg, read_cr3, write_cr3};
+use crate::paging::*;
+use crate::paging::page::*;
+use crate::paging::page_directory::*;
+use crate::paging::pdp::*;
+use crate::paging::pml4::*;
+
+const PRESENT: u64 = 1 << 0;
+const WRITABLE: u64 = 1 << 1;
+const USER: u64 = 1 << 2;
+const HUGE_PAGE: u64 = 1 << 7;
+const PAT: u64 = 1 << 7;
+const HUGE_PAT: u64 = 1 << 12;
+
+pub trait FrameAllocator {
+    fn allocate_frame(&mut self) -> Option<PhysicalAddress>;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MapperError {
+    NotAligned,
+    FrameAllocationFailed,
+    AlreadyMapped,
+    NotMapped,
+    HugePage,
+    HugePageNotSupported,
+}
+
+pub struct Mapper {
+    pml4_table_address: PhysicalAddress,
+    physical_memory_offset: u64,
+}
+
+impl Mapper {
+    pub unsafe fn new(pml4_table_address: PhysicalAddress, physical_memory_offset: u64) -> Self {
+        Self { pml4_table_address, physical_memory_offset }
+    }
+
+    pub unsafe fn current(physical_memory_offset: u64) -> Self {
+        Self::new(CR3::read().pml4_table_address(), physical_memory_offset)
+    }
+
+    pub fn pml4_table_address(&self) -> PhysicalAddress {
+        self.pml4_table_address
+    }
+
+    pub fn physical_memory_offset(&self) -> u64 {
+        self.physical_memory_offset
+    }
+
+    pub fn supports_1gb_pages() -> bool {
+        use core::arch::x86_64::__cpuid;
+        unsafe {
+            __cpuid(0x8000_0000).eax >= 0x8000_0001 && __cpuid(0x8000_0001).edx & (1 << 26) != 0
+        }
+    }
+
+    pub unsafe fn map<A: FrameAllocator>(
+        &mut self,
+        page: VirtualAddress,
+        frame: PhysicalAddress,
+        flags: PTEntryFlags,
+        allocator: &mut A,
+    ) -> Result<(), MapperError> {
+        self.map_sized(page, frame, PageSize::Size4KB, flags, allocator)
+    }
+
+    pub unsafe fn map_sized<A: FrameAllocator>(
+        &mut self,
+        page: VirtualAddress,
+        frame: PhysicalAddress,
+        size: PageSize,
+        flags: PTEntryFlags,
+        allocator: &mut A,
+    ) -> Result<(), MapperError> {
+        if !page.is_aligned(size.bytes()) || !frame.is_aligned(size.bytes()) {
+            return Err(MapperError::NotAligned);
+        }
+        if size == PageSize::Size1GB && !Self::supports_1gb_pages() {
+            return Err(MapperError::HugePageNotSupported);
+        }
+
+        let parent_flags = parent_flags(flags.bits());
+        let leaf_flags = flags.bits() | PRESENT;
+        let mut table = self.pml4_table();
+        let address = self.next_table_create(
+            &mut table[page.pml4_table_index()], parent_flags, allocator)?;
+
+        let mut table = self.pdp_table(address);
+        let entry = &mut table[page.pdp_table_index()];
+        if size == PageSize::Size1GB {
+            return set_huge_entry(entry, frame, leaf_flags);
+        }
+        let address = self.next_table_create(entry, parent_flags, allocator)?;
+
+        let mut table = self.page_directory(address);
+        let entry = &mut table[page.pd_table_index()];
+        if size == PageSize::Size2MB {
+            return set_huge_entry(entry, frame, leaf_flags);
+        }
+        let address = self.next_table_create(entry, parent_flags, allocator)?;
+
+        let mut table = self.page_table(address);
+        let entry = &mut table[page.page_table_index()];
+        if entry.bits() & PRESENT != 0 {
+            return Err(MapperError::AlreadyMapped);
+        }
+        entry.set_bits(frame.as_u64() | leaf_flags);
+        Ok(())
+    }
+
+    pub unsafe fn map_range<A: FrameAllocator>(
+        &mut self,
+        page: VirtualAddress,
+        frame: PhysicalAddress,
+        length: u64,
+        flags: PTEntryFlags,
+        allocator: &mut A,
+    ) -> Result<(), MapperError> {
+        let supports_1gb_pages = Self::supports_1gb_pages();
+        let mut offset = 0;
+        while offset < length {
+            let page = VirtualAddress::new(page.as_u64() + offset);
+            let frame = PhysicalAddress::new(frame.as_u64() + offset);
+            let remaining = length - offset;
+            let size = [PageSize::Size1GB, PageSize::Size2MB, PageSize::Size4KB].iter()
+                .copied()
+                .filter(|&size| size != PageSize::Size1GB || supports_1gb_pages)
+                .find(|&size| page.is_aligned(size.bytes()) && frame.is_aligned(size.bytes())
+                    && remaining >= size.bytes())
+                .unwrap_or(PageSize::Size4KB);
+            self.map_sized(page, frame, size, flags, allocator)?;
+            offset += size.bytes();
+        }
+        Ok(())
+    }
+
+    pub unsafe fn unmap(&mut self, page: VirtualAddress) -> Result<PhysicalAddress, MapperError> {
+        if !page.is_aligned(PAGE_SIZE_4KB) {
+            return Err(MapperError::NotAligned);
+        }
+
+        let mut table = self.page_table(self.find_page_table(page)?);
+        let entry = &mut table[page.page_table_index()];
+        if !entry.flags().contains(PTEntryFlags::PRESENT) {
+            return Err(MapperError::NotMapped);
+        }
+        let frame = entry.address();
+        entry.set_unused();
+        invlpg(page.as_u64());
+        Ok(frame)
+    }
+
+    pub unsafe fn update_flags(&mut self, page: VirtualAddress, flags: PTEntryFlags)
+                               -> Result<(), MapperError> {
+        if !page.is_aligned(PAGE_SIZE_4KB) {
+            return Err(MapperError::NotAligned);
+        }
+
+        let mut table = self.page_table(self.find_page_table(page)?);
+        let entry = &mut table[page.page_table_index()];
+        if !entry.flags().contains(PTEntryFlags::PRESENT) {
+            return Err(MapperError::NotMapped);
+        }
+        entry.set_bits(entry.address().as_u64() | flags.bits() | PRESENT);
+        invlpg(page.as_u64());
+        Ok(())
+    }
+
+    pub unsafe fn update_flags_split<A: FrameAllocator>(
+        &mut self,
+        page: VirtualAddress,
+        flags: PTEntryFlags,
+        allocator: &mut A,
+    ) -> Result<(), MapperError> {
+        loop {
+            match self.update_flags(page, flags) {
+                Err(MapperError::HugePage) => self.split(page, allocator)?,
+                result => return result,
+            }
+        }
+    }
+
+    pub unsafe fn split<A: FrameAllocator>(&mut self, address: VirtualAddress, allocator: &mut A)
+                                           -> Result<(), MapperError> {
+        let table = self.pml4_table();
+        let mut table = self.pdp_table(next_table(&table[address.pml4_table_index()])?);
+        let entry = &mut table[address.pdp_table_index()];
+        if entry.bits() & (PRESENT | HUGE_PAGE) == PRESENT | HUGE_PAGE {
+            let frame = entry.address().as_u64();
+            let flags = entry.bits() & !ADDRESS_MASK_1GB;
+            let table_address = self.allocate_table(allocator)?;
+            let mut directory = self.page_directory(table_address);
+            for (i, huge_entry) in directory.iter_mut().enumerate() {
+                huge_entry.set_bits((frame + i as u64 * PAGE_SIZE_2MB) | flags);
+            }
+            entry.set_bits(table_address.as_u64() | parent_flags(flags));
+            invlpg(align_down(address.as_u64(), PAGE_SIZE_1GB));
+            return Ok(());
+        }
+
+        let mut table = self.page_directory(next_table(entry)?);
+        let entry = &mut table[address.pd_table_index()];
+        if entry.bits() & (PRESENT | HUGE_PAGE) == PRESENT | HUGE_PAGE {
+            let frame = entry.address().as_u64();
+            let flags = small_page_flags(entry.bits() & !ADDRESS_MASK_2MB);
+            let table_address = self.allocate_table(allocator)?;
+            let mut page_table = self.page_table(table_address);
+            for (i, page_entry) in page_table.iter_mut().enumerate() {
+                page_entry.set_bits((frame + i as u64 * PAGE_SIZE_4KB) | flags);
+            }
+            entry.set_bits(table_address.as_u64() | parent_flags(flags));
+            invlpg(align_down(address.as_u64(), PAGE_SIZE_2MB));
+            return Ok(());
+        }
+
+        next_table(entry)?;
+        Ok(())
+    }
+
+    pub unsafe fn unmap_lower_half(&mut self) {
+        let mut table = self.pml4_table();
+        for entry in table.iter_mut().take(TABLE_ENTRIES / 2) {
+            entry.set_bits(0);
+        }
+        if CR3::read().pml4_table_address() == self.pml4_table_address {
+            write_cr3(read_cr3());
+        }
+    }
+
+    pub fn translate(&self, address: VirtualAddress) -> Option<PhysicalAddress> {
+        let (frame, size) = self.translate_page(address)?;
+        Some(PhysicalAddress::new(frame.as_u64() + (address.as_u64() & (size.bytes() - 1))))
+    }
+
+    pub fn translate_page(&self, address: VirtualAddress) -> Option<(PhysicalAddress, PageSize)> {
+        unsafe {
+            let table = self.pml4_table();
+            let entry = &table[address.pml4_table_index()];
+            if !entry.flags().contains(PML4EntryFlags::PRESENT) {
+                return None;
+            }
+
+            let table = self.pdp_table(entry.address());
+            let entry = &table[address.pdp_table_index()];
+            if !entry.flags().contains(PDPTEntryFlags::PRESENT) {
+                return None;
+            }
+            if entry.flags().contains(PDPTEntryFlags::PAGE_SIZE) {
+                return Some((entry.address(), PageSize::Size1GB));
+            }
+
+            let table = self.page_directory(entry.address());
+            let entry = &table[address.pd_table_index()];
+            if !entry.flags().contains(PDEntryFlags::PRESENT) {
+                return None;
+            }
+            if entry.flags().contains(PDEntryFlags::PAGE_SIZE) {
+                return Some((entry.address(), PageSize::Size2MB));
+            }
+
+            let table = self.page_table(entry.address());
+            let entry = &table[address.page_table_index()];
+            if !entry.flags().contains(PTEntryFlags::PRESENT) {
+                return None;
+            }
+            Some((entry.address(), PageSize::Size4KB))
+        }
+    }
+
+    fn table_address(&self, address: PhysicalAddress) -> VirtualAddress {
+        address.to_virtual(self.physical_memory_offset)
+    }
+
+    unsafe fn pml4_table(&self) -> PML4Table<'static> {
+        PML4Table::from_virtual_address(self.table_address(self.pml4_table_address))
+    }
+
+    unsafe fn pdp_table(&self, address: PhysicalAddress) -> PDPTable<'static> {
+        PDPTable::from_virtual_address(self.table_address(address))
+    }
+
+    unsafe fn page_directory(&self, address: PhysicalAddress) -> PageDirectory<'static> {
+        PageDirectory::from_virtual_address(self.table_address(address))
+    }
+
+    unsafe fn page_table(&self, address: PhysicalAddress) -> PageTable<'static> {
+        PageTable::from_virtual_address(self.table_address(address))
+    }
+
+    unsafe fn allocate_table<A: FrameAllocator>(&self, allocator: &mut A)
+                                                -> Result<PhysicalAddress, MapperError> {
+        let frame = allocator.allocate_frame().ok_or(MapperError::FrameAllocationFailed)?;
+        ptr::write_bytes(self.table_address(frame).as_mut_ptr::<u8>(), 0, PAGE_SIZE_4KB as usize);
+        Ok(frame)
+    }
+
+    unsafe fn next_table_create<E: PageEntry, A: FrameAllocator>(
+        &self,
+        entry: &mut E,
+        flags: u64,
+        allocator: &mut A,
+    ) -> Result<PhysicalAddress, MapperError> {
+        if entry.bits() & HUGE_PAGE != 0 {
+            return Err(MapperError::HugePage);
+        }
+        if entry.bits() & PRESENT == 0 {
+            entry.set_bits(self.allocate_table(allocator)?.as_u64());
+        }
+        entry.set_flags(E::Flags::from_bits_truncate(flags));
+        Ok(entry.address())
+    }
+
+    unsafe fn find_page_table(&self, page: VirtualAddress) -> Result<PhysicalAddress, MapperError> {
+        let table = self.pml4_table();
+        let address = next_table(&table[page.pml4_table_index()])?;
+        let table = self.pdp_table(address);
+        let address = next_table(&table[page.pdp_table_index()])?;
+        let table = self.page_directory(address);
+        next_table(&table[page.pd_table_index()])
+    }
+}
+
+fn parent_flags(flags: u64) -> u64 {
+    PRESENT | WRITABLE | (flags & USER)
+}
+
+fn small_page_flags(huge_flags: u64) -> u64 {
+    let flags = huge_flags & !(HUGE_PAGE | HUGE_PAT);
+    if huge_flags & HUGE_PAT != 0 { flags | PAT } else { flags }
+}
+
+fn huge_page_flags(flags: u64) -> u64 {
+    let huge_flags = (flags & !PAT) | HUGE_PAGE;
+    if flags & PAT != 0 { huge_flags | HUGE_PAT } else { huge_flags }
+}
+
+unsafe fn set_huge_entry<E: PageEntry>(entry: &mut E, frame: PhysicalAddress, flags: u64)
+                                       -> Result<(), MapperError> {
+    if entry.bits() & PRESENT != 0 {
+        return Err(MapperError::AlreadyMapped);
+    }
+    entry.set_bits(frame.as_u64() | huge_page_flags(flags));
+    Ok(())
+}
+
+fn next_table<E: PageEntry>(entry: &E) -> Result<PhysicalAddress, MapperError> {
+    if entry.bits() & PRESENT == 0 {
+        Err(MapperError::NotMapped)
+    } else if entry.bits() & HUGE_PAGE != 0 {
+        Err(MapperError::HugePage)
+    } else {
+        Ok(entry.address())
+    }
+}
@@ -4,6 +4,7 @@ pub mod pml4;
 pub mod pdp;
 pub mod page_directory;
 pub mod page;
+pub mod mapper;
 
 pub const TABLE_ENTRIES: usize = 512;
 pub const PAGE_SIZE_1GB: u64 = 0x40000000;
@@ -14,6 +15,23 @@ pub const ADDRESS_MASK_1GB: u64 = 0xf_ffff_c000_0000;
 pub const ADDRESS_MASK_2MB: u64 = 0xf_ffff_ffe0_0000;
 pub const ADDRESS_MASK_4KB: u64 = 0xf_ffff_ffff_f000;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PageSize {
+    Size4KB,
+    Size2MB,
+    Size1GB,
+}
+
+impl PageSize {
+    pub fn bytes(self) -> u64 {
+        match self {
+            PageSize::Size4KB => PAGE_SIZE_4KB,
+            PageSize::Size2MB => PAGE_SIZE_2MB,
+            PageSize::Size1GB => PAGE_SIZE_1GB,
+        }
+    }
+}
+
 pub trait PageEntryFlags {
     fn from_bits_truncate(bits: u64) -> Self;
 
@@ -1,9 +1,10 @@
-use crate::address::PhysicalAddress;
+use crate::address::{PhysicalAddress, VirtualAddress};
 use crate::paging::*;
 use core::ops::{BitOr, Index, IndexMut};
 use core::{fmt, slice};
 use core::convert::TryInto;
 
+#[derive(Copy, Clone, Eq, PartialEq)]
 pub struct PDEntryFlags(u64);
 
 impl PDEntryFlags {
@@ -89,9 +90,13 @@ pub struct PageDirectory<'a> {
 
 impl<'a> PageDirectory<'a> {
     pub unsafe fn from_address(address: PhysicalAddress) -> Self {
+        Self::from_virtual_address(VirtualAddress::new(address.as_u64()))
+    }
+
+    pub unsafe fn from_virtual_address(address: VirtualAddress) -> Self {
         Self {
             entries: slice::from_raw_parts_mut(
-                address.as_u64() as *mut PDEntry,
+                address.as_mut_ptr::<PDEntry>(),
                 TABLE_ENTRIES,
             ).try_into().unwrap()
         }
@@ -1,9 +1,10 @@
-use crate::address::PhysicalAddress;
+use crate::address::{PhysicalAddress, VirtualAddress};
 use crate::paging::*;
 use core::ops::{BitOr, Index, IndexMut};
 use core::{fmt, slice};
 use core::convert::TryInto;
 
+#[derive(Copy, Clone, Eq, PartialEq)]
 pub struct PML4EntryFlags(u64);
 
 impl PML4EntryFlags {
@@ -74,9 +75,13 @@ pub struct PML4Table<'a> {
 
 impl<'a> PML4Table<'a> {
     pub unsafe fn from_address(address: PhysicalAddress) -> Self {
+        Self::from_virtual_address(VirtualAddress::new(address.as_u64()))
+    }
+
+    pub unsafe fn from_virtual_address(address: VirtualAddress) -> Self {
         Self {
             entries: slice::from_raw_parts_mut(
-                address.as_u64() as *mut PML4Entry,
+                address.as_mut_ptr::<PML4Entry>(),
                 TABLE_ENTRIES,
             ).try_into().unwrap()
         }
@@ -0,0 +1,67 @@
+#![no_main]
+
+use elf::loader::ELF64Loader;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut words = vec![0u64; data.len() / 8 + 1];
+    let buffer = unsafe {
+        std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, data.len())
+    };
+    buffer.copy_from_slice(data);
+
+    let loader = match ELF64Loader::new(buffer) {
+        Ok(loader) => loader,
+        Err(_) => return,
+    };
+
+    if let Ok(program_headers) = loader.program_header_iter() {
+        for program_header in program_headers {
+            let _ = program_header.segment_pages(0x1000);
+        }
+    }
+    if let Ok(entries) = loader.dynamic_entries() {
+        entries.for_each(drop);
+    }
+    if let Ok(relocations) = loader.relocations() {
+        for relocation in relocations {
+            let _ = loader.dynamic_symbol(relocation.symbol_index());
+        }
+    }
+    if let Ok(section_headers) = loader.section_header_iter() {
+        for section_header in section_headers {
+            let _ = loader.section_name(section_header);
+            let _ = loader.section_data(section_header);
+        }
+    }
+    if let Ok(notes) = loader.notes() {
+        for note in notes {
+            let _ = (note.name(), note.build_id(), note.boot_protocol_version());
+        }
+    }
+    if let Ok(notes) = loader.section_notes() {
+        for note in notes {
+            let _ = (note.name(), note.build_id(), note.boot_protocol_version());
+        }
+    }
+    let _ = loader.build_id();
+    let _ = loader.boot_protocol_version();
+    if let Ok(symbols) = loader.symbol_table() {
+        for symbol in symbols.iter() {
+            let _ = symbols.name(symbol);
+        }
+        let _ = symbols.find_by_address(loader.file_header().entry_point());
+    }
+
+    let mut image = vec![0u8; 0x1_0000];
+    let image_start = image.as_mut_ptr() as usize;
+    let _ = unsafe {
+        loader.apply_relocations(0xffff_ffff_8000_0000, |address| {
+            if address.checked_add(8)? <= image.len() as u64 {
+                Some((image_start + address as usize) as *mut u8)
+            } else {
+                None
+            }
+        })
+    };
+});
@@ -0,0 +1,117 @@
+use core::fmt;
+
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct DynamicTag(i64);
+
+impl DynamicTag {
+    pub const NULL: Self = Self(0);
+    pub const NEEDED: Self = Self(1);
+    pub const PLTRELSZ: Self = Self(2);
+    pub const PLTGOT: Self = Self(3);
+    pub const HASH: Self = Self(4);
+    pub const STRTAB: Self = Self(5);
+    pub const SYMTAB: Self = Self(6);
+    pub const RELA: Self = Self(7);
+    pub const RELASZ: Self = Self(8);
+    pub const RELAENT: Self = Self(9);
+    pub const STRSZ: Self = Self(10);
+    pub const SYMENT: Self = Self(11);
+    pub const INIT: Self = Self(12);
+    pub const FINI: Self = Self(13);
+    pub const SONAME: Self = Self(14);
+    pub const REL: Self = Self(17);
+    pub const RELSZ: Self = Self(18);
+    pub const RELENT: Self = Self(19);
+    pub const PLTREL: Self = Self(20);
+    pub const DEBUG: Self = Self(21);
+    pub const TEXTREL: Self = Self(22);
+    pub const JMPREL: Self = Self(23);
+    pub const BIND_NOW: Self = Self(24);
+    pub const FLAGS: Self = Self(30);
+    pub const GNU_HASH: Self = Self(0x6ffffef5);
+    pub const RELACOUNT: Self = Self(0x6ffffff9);
+    pub const FLAGS_1: Self = Self(0x6ffffffb);
+}
+
+impl fmt::Debug for DynamicTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NULL => write!(f, "Null"),
+            Self::NEEDED => write!(f, "Needed"),
+            Self::PLTRELSZ => write!(f, "PltRelocationSize"),
+            Self::PLTGOT => write!(f, "PltGot"),
+            Self::HASH => write!(f, "Hash"),
+            Self::STRTAB => write!(f, "StringTable"),
+            Self::SYMTAB => write!(f, "SymbolTable"),
+            Self::RELA => write!(f, "Rela"),
+            Self::RELASZ => write!(f, "RelaSize"),
+            Self::RELAENT => write!(f, "RelaEntrySize"),
+            Self::STRSZ => write!(f, "StringTableSize"),
+            Self::SYMENT => write!(f, "SymbolEntrySize"),
+            Self::INIT => write!(f, "Init"),
+            Self::FINI => write!(f, "Fini"),
+            Self::SONAME => write!(f, "SharedObjectName"),
+            Self::REL => write!(f, "Rel"),
+            Self::RELSZ => write!(f, "RelSize"),
+            Self::RELENT => write!(f, "RelEntrySize"),
+            Self::PLTREL => write!(f, "PltRelocationType"),
+            Self::DEBUG => write!(f, "Debug"),
+            Self::TEXTREL => write!(f, "TextRelocation"),
+            Self::JMPREL => write!(f, "JumpRelocation"),
+            Self::BIND_NOW => write!(f, "BindNow"),
+            Self::FLAGS => write!(f, "Flags"),
+            Self::GNU_HASH => write!(f, "GnuHash"),
+            Self::RELACOUNT => write!(f, "RelaCount"),
+            Self::FLAGS_1 => write!(f, "Flags1"),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct DynamicEntry {
+    tag: DynamicTag,
+    value: u64,
+}
+
+impl DynamicEntry {
+    pub fn tag(&self) -> DynamicTag {
+        self.tag
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+pub struct DynamicIter<'a> {
+    entries: &'a [DynamicEntry],
+    index: usize,
+}
+
+impl<'a> DynamicIter<'a> {
+    pub(crate) fn new(buffer: &'a [u8]) -> Self {
+        let entries = unsafe {
+            core::slice::from_raw_parts(
+                buffer.as_ptr() as *const DynamicEntry,
+                buffer.len() / core::mem::size_of::<DynamicEntry>(),
+            )
+        };
+        Self { entries, index: 0 }
+    }
+}
+
+impl<'a> Iterator for DynamicIter<'a> {
+    type Item = &'a DynamicEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.get(self.index)?;
+        if entry.tag() == DynamicTag::NULL {
+            return None;
+        }
+        self.index += 1;
+        Some(entry)
+    }
+}
@@ -1,8 +1,29 @@
+use crate::file_header::Machine;
+use crate::relocation::RelocationType;
+
 #[derive(Debug)]
 pub enum Error {
     BufferSizeTooSmall,
+    BufferNotAligned,
     NotELF64,
+    UnsupportedDataEncoding(u8),
+    UnsupportedFileType(u16),
+    UnsupportedMachine(Machine),
+    UnsupportedVersion(u32),
+    InvalidEntrySize(usize),
+    TableNotAligned(u64),
+    OffsetOverflow,
+    AddressOverflow(u64),
+    FileSizeExceedsMemorySize(u64),
+    OverlappingSegments(u64),
     ProgramHeaderNotExist,
+    SectionHeaderNotExist,
+    SectionNotExist,
     PageSizeNotPowerOfTwo,
-    PageNotAlignment
+    PageNotAlignment,
+    DynamicSegmentNotExist,
+    AddressNotLoaded(u64),
+    UnsupportedRelocation(RelocationType),
+    SymbolTableNotExist,
+    UndefinedSymbol(u32),
 }
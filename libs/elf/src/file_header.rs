@@ -1,11 +1,37 @@
 use super::{Identification, FileType};
+use core::fmt;
+
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Machine(u16);
+
+impl Machine {
+    pub const NONE: Self = Self(0);
+    pub const X86: Self = Self(3);
+    pub const X86_64: Self = Self(62);
+    pub const AARCH64: Self = Self(183);
+    pub const RISCV: Self = Self(243);
+}
+
+impl fmt::Debug for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NONE => write!(f, "None"),
+            Self::X86 => write!(f, "X86"),
+            Self::X86_64 => write!(f, "X86_64"),
+            Self::AARCH64 => write!(f, "AArch64"),
+            Self::RISCV => write!(f, "RISC-V"),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
+}
 
 #[repr(C)]
 #[derive(Debug)]
 pub struct FileHeader64 {
     ident: Identification,
     file_type: FileType,
-    machine: u16,
+    machine: Machine,
     version: u32,
     entry: u64,
     program_header_offset: u64,
@@ -28,6 +54,14 @@ impl FileHeader64 {
         self.file_type
     }
 
+    pub fn machine(&self) -> Machine {
+        self.machine
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
     pub fn entry_point(&self) -> u64 {
         self.entry
     }
@@ -43,4 +77,20 @@ impl FileHeader64 {
     pub fn program_entries(&self) -> usize {
         self.program_header_entries as usize
     }
+
+    pub fn section_header_offset(&self) -> usize {
+        self.section_header_offset as usize
+    }
+
+    pub fn section_entry_size(&self) -> usize {
+        self.section_header_entry_size as usize
+    }
+
+    pub fn section_entries(&self) -> usize {
+        self.section_header_entries as usize
+    }
+
+    pub fn section_name_string_table_index(&self) -> usize {
+        self.section_name_string_table_index as usize
+    }
 }
@@ -1,4 +1,7 @@
 #![no_std]
+// The crate is built with the same older nightly as the bootloader and kernel, which predates
+// the std methods these lints suggest.
+#![allow(unknown_lints, clippy::manual_is_multiple_of, clippy::unnecessary_map_or)]
 
 pub mod error;
 pub mod result;
@@ -6,9 +9,21 @@ pub mod identification;
 pub mod file_type;
 pub mod file_header;
 pub mod program_header;
+pub mod section_header;
+pub mod string_table;
+pub mod dynamic;
+pub mod relocation;
+pub mod symbol;
+pub mod note;
 pub mod loader;
 
 pub use identification::*;
 pub use file_type::*;
 pub use file_header::*;
 pub use program_header::*;
+pub use section_header::*;
+pub use string_table::*;
+pub use dynamic::*;
+pub use relocation::*;
+pub use symbol::*;
+pub use note::*;
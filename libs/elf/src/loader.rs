@@ -1,9 +1,13 @@
 use crate::result::Result;
 use crate::error::Error;
-use crate::{SegmentType, ProgramHeader};
-use super::{FileHeader64, Class, ProgramHeaderIter};
+use crate::{SegmentType, ProgramHeader, FileType, DynamicIter, DynamicTag, RelaIter, Rela,
+            RelocationType, DynamicEntry, Symbol, SymbolBinding, SectionHeader,
+            SectionHeaderIter, SectionType, StringTable, SymbolTable, Note, NoteIter};
+use super::{FileHeader64, Class, DataEncoding, Machine, ProgramHeaderIter};
 use core::{mem, ptr, slice};
 
+const CURRENT_VERSION: u32 = 1;
+
 pub struct ELF64Loader<'a> {
     buffer: &'a [u8],
     file_header: &'a FileHeader64,
@@ -15,19 +19,114 @@ impl<'a> ELF64Loader<'a> {
             return Err(Error::BufferSizeTooSmall);
         }
 
-        let file_header = unsafe { &*(buffer.as_ptr() as *const FileHeader64) };
-        let elf_ident = file_header.identification();
-        if !(elf_ident.is_elf() && elf_ident.class() == Class::Class64) {
+        if buffer.as_ptr() as usize % mem::align_of::<FileHeader64>() != 0 {
+            return Err(Error::BufferNotAligned);
+        }
+
+        const ELF_MAGIC_NUMBER: [u8; 4] = [0x7f, 0x45, 0x4c, 0x46];
+        if buffer[0..4] != ELF_MAGIC_NUMBER || buffer[4] != Class::Class64 as u8 {
             return Err(Error::NotELF64);
         }
+        if buffer[5] != DataEncoding::LittleEndian as u8 {
+            return Err(Error::UnsupportedDataEncoding(buffer[5]));
+        }
+        let file_type = u16::from_le_bytes([buffer[16], buffer[17]]);
+        if file_type > FileType::CORE as u16 {
+            return Err(Error::UnsupportedFileType(file_type));
+        }
+
+        let file_header = unsafe { &*(buffer.as_ptr() as *const FileHeader64) };
+        if file_header.machine() != Machine::X86_64 {
+            return Err(Error::UnsupportedMachine(file_header.machine()));
+        }
+        if file_header.version() != CURRENT_VERSION {
+            return Err(Error::UnsupportedVersion(file_header.version()));
+        }
+
+        let loader = ELF64Loader { buffer, file_header };
+        loader.validate_program_headers()?;
+        loader.validate_section_headers()?;
+        Ok(loader)
+    }
+
+    fn validate_program_headers(&self) -> Result {
+        if self.file_header.program_entries() == 0 {
+            return Ok(());
+        }
+        self.table::<ProgramHeader>(
+            self.file_header.program_header_offset() as u64,
+            self.file_header.program_entries(),
+            self.file_header.program_entry_size(),
+        )?;
+
+        for program_header in self.program_header_iter()? {
+            if program_header.segment_file_size() > program_header.segment_memory_size() {
+                return Err(Error::FileSizeExceedsMemorySize(program_header.start_address()));
+            }
+            self.range(program_header.offset(), program_header.segment_file_size())?;
+            if program_header.start_address()
+                .checked_add(program_header.segment_memory_size()).is_none() {
+                return Err(Error::AddressOverflow(program_header.start_address()));
+            }
+        }
 
-        Ok(ELF64Loader { buffer, file_header })
+        let loads = self.program_header_iter()?
+            .filter(|program_header| program_header.segment_type() == SegmentType::LOAD);
+        for (index, program_header) in loads.enumerate() {
+            let start = program_header.start_address();
+            let end = start + program_header.segment_memory_size();
+            let overlapped = self.program_header_iter()?
+                .filter(|other| other.segment_type() == SegmentType::LOAD)
+                .skip(index + 1)
+                .any(|other| {
+                    let other_end = other.start_address() + other.segment_memory_size();
+                    start < other_end && other.start_address() < end
+                });
+            if overlapped {
+                return Err(Error::OverlappingSegments(start));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_section_headers(&self) -> Result {
+        if self.file_header.section_entries() == 0 {
+            return Ok(());
+        }
+        self.table::<SectionHeader>(
+            self.file_header.section_header_offset() as u64,
+            self.file_header.section_entries(),
+            self.file_header.section_entry_size(),
+        )?;
+        Ok(())
+    }
+
+    fn range(&self, offset: u64, size: u64) -> Result<&'a [u8]> {
+        let end = offset.checked_add(size).ok_or(Error::OffsetOverflow)?;
+        if end > self.buffer.len() as u64 {
+            return Err(Error::BufferSizeTooSmall);
+        }
+        Ok(&self.buffer[offset as usize..end as usize])
+    }
+
+    fn table<T>(&self, offset: u64, entries: usize, entry_size: usize) -> Result<&'a [u8]> {
+        if entry_size < mem::size_of::<T>() || entry_size % mem::align_of::<T>() != 0 {
+            return Err(Error::InvalidEntrySize(entry_size));
+        }
+        if offset % mem::align_of::<T>() as u64 != 0 {
+            return Err(Error::TableNotAligned(offset));
+        }
+        let size = (entries as u64).checked_mul(entry_size as u64).ok_or(Error::OffsetOverflow)?;
+        self.range(offset, size)
     }
 
     pub fn file_header(&self) -> &FileHeader64 {
-        &self.file_header
+        self.file_header
     }
 
+    /// # Safety
+    ///
+    /// Every LOAD segment's virtual address range must be mapped, writable and unused.
     pub unsafe fn load_programs(&self) -> Result {
         for program_header in self.program_header_iter()? {
             if program_header.segment_type() != SegmentType::LOAD {
@@ -51,7 +150,17 @@ impl<'a> ELF64Loader<'a> {
         Ok(())
     }
 
+    /// # Safety
+    ///
+    /// The segment's virtual address range must be mapped, writable and unused.
     pub unsafe fn load_program(&self, program_header: &ProgramHeader) {
+        self.load_program_at(program_header, program_header.start_address() as usize)
+    }
+
+    /// # Safety
+    ///
+    /// `address_start` must point to writable memory of at least the segment's memory size.
+    pub unsafe fn load_program_at(&self, program_header: &ProgramHeader, address_start: usize) {
         if program_header.segment_type() != SegmentType::LOAD {
             return
         }
@@ -59,7 +168,6 @@ impl<'a> ELF64Loader<'a> {
         let file_size = program_header.segment_file_size() as usize;
         let offset_start = program_header.offset() as usize;
         let offset_end = offset_start + file_size;
-        let address_start = program_header.start_address() as usize;
 
         slice::from_raw_parts_mut(address_start as *mut u8, file_size)
             .copy_from_slice(&self.buffer[offset_start..offset_end]);
@@ -71,20 +179,229 @@ impl<'a> ELF64Loader<'a> {
         );
     }
 
-    pub fn program_header_iter(&self) -> Result<ProgramHeaderIter> {
+    pub fn is_position_independent(&self) -> bool {
+        self.file_header.file_type() == FileType::SHARED
+    }
+
+    pub fn dynamic_entries(&self) -> Result<DynamicIter<'_>> {
+        let program_header = self.program_header_iter()?
+            .find(|program_header| program_header.segment_type() == SegmentType::DYNAMIC)
+            .ok_or(Error::DynamicSegmentNotExist)?;
+        let entry_size = mem::size_of::<DynamicEntry>();
+        let buffer = self.table::<DynamicEntry>(
+            program_header.offset(),
+            program_header.segment_file_size() as usize / entry_size,
+            entry_size,
+        )?;
+        Ok(DynamicIter::new(buffer))
+    }
+
+    pub fn relocations(&self) -> Result<RelaIter<'_>> {
+        let mut address = None;
+        let mut size = 0;
+        let mut entry_size = mem::size_of::<Rela>();
+        for entry in self.dynamic_entries()? {
+            match entry.tag() {
+                DynamicTag::RELA => address = Some(entry.value()),
+                DynamicTag::RELASZ => size = entry.value() as usize,
+                DynamicTag::RELAENT => entry_size = entry.value() as usize,
+                _ => {}
+            }
+        }
+
+        let buffer = match address {
+            Some(address) => {
+                let entries = size / entry_size.max(1);
+                self.table::<Rela>(self.file_offset(address)?, entries, entry_size)?
+            }
+            None => &[]
+        };
+        Ok(RelaIter::new(buffer, entry_size))
+    }
+
+    pub fn dynamic_symbol(&self, index: u32) -> Result<&Symbol> {
+        let mut address = None;
+        let mut entry_size = mem::size_of::<Symbol>();
+        for entry in self.dynamic_entries()? {
+            match entry.tag() {
+                DynamicTag::SYMTAB => address = Some(entry.value()),
+                DynamicTag::SYMENT => entry_size = entry.value() as usize,
+                _ => {}
+            }
+        }
+
+        let address = (index as u64).checked_mul(entry_size as u64)
+            .and_then(|offset| address?.checked_add(offset))
+            .ok_or(Error::SymbolTableNotExist)?;
+        let buffer = self.table::<Symbol>(self.file_offset(address)?, 1, entry_size)?;
+        Ok(unsafe { &*(buffer.as_ptr() as *const Symbol) })
+    }
+
+    fn symbol_address(&self, index: u32, load_bias: u64) -> Result<u64> {
+        let symbol = self.dynamic_symbol(index)?;
+        if !symbol.is_undefined() {
+            Ok(load_bias.wrapping_add(symbol.value()))
+        } else if symbol.binding() == SymbolBinding::WEAK {
+            Ok(0)
+        } else {
+            Err(Error::UndefinedSymbol(index))
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `translate` must return writable pointers into the loaded image for relocation targets.
+    pub unsafe fn apply_relocations<F>(&self, load_bias: u64, translate: F) -> Result
+        where F: Fn(u64) -> Option<*mut u8> {
+        for relocation in self.relocations()? {
+            let value = match relocation.relocation_type() {
+                RelocationType::X86_64_NONE => continue,
+                RelocationType::X86_64_RELATIVE =>
+                    load_bias.wrapping_add(relocation.addend() as u64),
+                RelocationType::X86_64_64 =>
+                    self.symbol_address(relocation.symbol_index(), load_bias)?
+                        .wrapping_add(relocation.addend() as u64),
+                RelocationType::X86_64_GLOB_DAT =>
+                    self.symbol_address(relocation.symbol_index(), load_bias)?,
+                relocation_type => return Err(Error::UnsupportedRelocation(relocation_type))
+            };
+            self.check_loaded(relocation.offset(), mem::size_of::<u64>() as u64)?;
+            let target = translate(relocation.offset())
+                .ok_or(Error::AddressNotLoaded(relocation.offset()))?;
+            (target as *mut u64).write_unaligned(value);
+        }
+        Ok(())
+    }
+
+    fn check_loaded(&self, address: u64, size: u64) -> Result {
+        let end = address.checked_add(size).ok_or(Error::AddressOverflow(address))?;
+        if self.program_header_iter()?
+            .filter(|program_header| program_header.segment_type() == SegmentType::LOAD)
+            .any(|program_header| program_header.start_address() <= address
+                && end <= program_header.start_address() + program_header.segment_memory_size()) {
+            Ok(())
+        } else {
+            Err(Error::AddressNotLoaded(address))
+        }
+    }
+
+    fn file_offset(&self, address: u64) -> Result<u64> {
+        self.program_header_iter()?
+            .filter(|program_header| program_header.segment_type() == SegmentType::LOAD)
+            .find(|program_header| program_header.start_address() <= address
+                && address < program_header.start_address() + program_header.segment_file_size())
+            .map(|program_header| {
+                address - program_header.start_address() + program_header.offset()
+            })
+            .ok_or(Error::AddressNotLoaded(address))
+    }
+
+    pub fn program_header_iter(&self) -> Result<ProgramHeaderIter<'_>> {
         let headers = self.file_header.program_entries();
         if headers == 0 {
             return Err(Error::ProgramHeaderNotExist);
         }
 
         let header_entry_size = self.file_header.program_entry_size();
-        let header_start_offset = self.file_header.program_header_offset();
-        let header_end_offset = header_start_offset + header_entry_size * headers;
-
         ProgramHeaderIter::new(
-            &self.buffer[header_start_offset..header_end_offset],
+            self.table::<ProgramHeader>(
+                self.file_header.program_header_offset() as u64,
+                headers,
+                header_entry_size,
+            )?,
+            headers,
+            header_entry_size,
+        )
+    }
+
+    pub fn notes(&self) -> Result<impl Iterator<Item=Note<'a>> + '_> {
+        Ok(self.program_header_iter()?
+            .filter(|program_header| program_header.segment_type() == SegmentType::NOTE)
+            .filter_map(move |program_header| {
+                let buffer = self.range(program_header.offset(),
+                                        program_header.segment_file_size()).ok()?;
+                Some(NoteIter::new(buffer, program_header.segment_alignment()))
+            })
+            .flatten())
+    }
+
+    pub fn section_notes(&self) -> Result<impl Iterator<Item=Note<'a>> + '_> {
+        Ok(self.section_header_iter()?
+            .filter(|section_header| section_header.section_type() == SectionType::NOTE)
+            .filter_map(move |section_header| {
+                let buffer = self.section_data(section_header).ok()?;
+                Some(NoteIter::new(buffer, section_header.alignment()))
+            })
+            .flatten())
+    }
+
+    pub fn build_id(&self) -> Option<&'a [u8]> {
+        self.notes().ok()?.find_map(|note| note.build_id())
+    }
+
+    pub fn boot_protocol_version(&self) -> Option<u32> {
+        self.notes().ok()?.find_map(|note| note.boot_protocol_version())
+    }
+
+    pub fn section_header_iter(&self) -> Result<SectionHeaderIter<'a>> {
+        let headers = self.file_header.section_entries();
+        if headers == 0 {
+            return Err(Error::SectionHeaderNotExist);
+        }
+
+        let header_entry_size = self.file_header.section_entry_size();
+        SectionHeaderIter::new(
+            self.table::<SectionHeader>(
+                self.file_header.section_header_offset() as u64,
+                headers,
+                header_entry_size,
+            )?,
             headers,
             header_entry_size,
         )
     }
+
+    pub fn section_header(&self, index: usize) -> Result<&'a SectionHeader> {
+        self.section_header_iter()?.nth(index).ok_or(Error::SectionNotExist)
+    }
+
+    pub fn section_data(&self, section_header: &SectionHeader) -> Result<&'a [u8]> {
+        if section_header.section_type() == SectionType::NO_BITS {
+            return Ok(&[]);
+        }
+        self.range(section_header.offset(), section_header.size())
+    }
+
+    pub fn section_names(&self) -> Result<StringTable<'a>> {
+        let index = self.file_header.section_name_string_table_index();
+        Ok(StringTable::new(self.section_data(self.section_header(index)?)?))
+    }
+
+    pub fn section_name(&self, section_header: &SectionHeader) -> Result<&'a str> {
+        self.section_names()?.get(section_header.name_offset()).ok_or(Error::SectionNotExist)
+    }
+
+    pub fn section_by_name(&self, name: &str) -> Result<&'a SectionHeader> {
+        let names = self.section_names()?;
+        self.section_header_iter()?
+            .find(|section_header| names.get(section_header.name_offset()) == Some(name))
+            .ok_or(Error::SectionNotExist)
+    }
+
+    pub fn symbol_table(&self) -> Result<SymbolTable<'a>> {
+        let section_header = self.section_header_iter()?
+            .find(|section_header| section_header.section_type() == SectionType::SYMBOL_TABLE)
+            .ok_or(Error::SymbolTableNotExist)?;
+        let strings = self.section_header(section_header.link() as usize)?;
+        let entry_size = section_header.entry_size() as usize;
+        Ok(SymbolTable::new(
+            self.table::<Symbol>(
+                section_header.offset(),
+                section_header.size() as usize / entry_size.max(1),
+                entry_size,
+            )?,
+            entry_size,
+            StringTable::new(self.section_data(strings)?),
+        ))
+    }
 }
@@ -0,0 +1,102 @@
+use core::{fmt, str};
+use core::convert::TryInto;
+
+pub const NOTE_NAME_GNU: &str = "GNU";
+pub const NOTE_NAME_MONORS: &str = "MonorsOS";
+
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct NoteType(u32);
+
+impl NoteType {
+    pub const GNU_ABI_TAG: Self = Self(1);
+    pub const GNU_BUILD_ID: Self = Self(3);
+    pub const MONORS_BOOT_PROTOCOL_VERSION: Self = Self(1);
+}
+
+impl fmt::Debug for NoteType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Note<'a> {
+    name: &'a [u8],
+    note_type: NoteType,
+    descriptor: &'a [u8],
+}
+
+impl<'a> Note<'a> {
+    pub fn name(&self) -> Option<&'a str> {
+        let length = self.name.iter().position(|&byte| byte == 0).unwrap_or(self.name.len());
+        str::from_utf8(&self.name[..length]).ok()
+    }
+
+    pub fn note_type(&self) -> NoteType {
+        self.note_type
+    }
+
+    pub fn descriptor(&self) -> &'a [u8] {
+        self.descriptor
+    }
+
+    pub fn is(&self, name: &str, note_type: NoteType) -> bool {
+        self.name() == Some(name) && self.note_type == note_type
+    }
+
+    pub fn build_id(&self) -> Option<&'a [u8]> {
+        if self.is(NOTE_NAME_GNU, NoteType::GNU_BUILD_ID) {
+            Some(self.descriptor)
+        } else {
+            None
+        }
+    }
+
+    pub fn boot_protocol_version(&self) -> Option<u32> {
+        if self.is(NOTE_NAME_MONORS, NoteType::MONORS_BOOT_PROTOCOL_VERSION) {
+            Some(u32::from_le_bytes(self.descriptor.get(0..4)?.try_into().ok()?))
+        } else {
+            None
+        }
+    }
+}
+
+pub struct NoteIter<'a> {
+    buffer: &'a [u8],
+    alignment: usize,
+}
+
+impl<'a> NoteIter<'a> {
+    pub fn new(buffer: &'a [u8], alignment: u64) -> Self {
+        let alignment = if alignment == 8 { 8 } else { 4 };
+        Self { buffer, alignment }
+    }
+
+    fn read_u32(&self, offset: usize) -> Option<u32> {
+        Some(u32::from_le_bytes(self.buffer.get(offset..offset + 4)?.try_into().ok()?))
+    }
+
+    fn align(&self, size: usize) -> Option<usize> {
+        Some(size.checked_add(self.alignment - 1)? & !(self.alignment - 1))
+    }
+}
+
+impl<'a> Iterator for NoteIter<'a> {
+    type Item = Note<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let name_size = self.read_u32(0)? as usize;
+        let descriptor_size = self.read_u32(4)? as usize;
+        let note_type = NoteType(self.read_u32(8)?);
+
+        let name_start = 12;
+        let descriptor_start = self.align(name_start + name_size)?;
+        let end = self.align(descriptor_start.checked_add(descriptor_size)?)?;
+        let name = self.buffer.get(name_start..name_start + name_size)?;
+        let descriptor = self.buffer.get(descriptor_start..descriptor_start + descriptor_size)?;
+
+        self.buffer = self.buffer.get(end..).unwrap_or(&[]);
+        Some(Note { name, note_type, descriptor })
+    }
+}
@@ -46,6 +46,10 @@ impl ProgramHeaderFlags {
     pub const EXECUTABLE: Self = Self(1 << 0);
     pub const WRITABLE: Self = Self(1 << 1);
     pub const READABLE: Self = Self(1 << 2);
+
+    pub fn contains(self, flags: Self) -> bool {
+        self.0 & flags.0 == flags.0
+    }
 }
 
 impl BitOr for ProgramHeaderFlags {
@@ -94,8 +98,14 @@ impl ProgramHeader {
         self.segment_memory_size
     }
 
+    pub fn segment_alignment(&self) -> u64 {
+        self.segment_alignment
+    }
+
     pub fn segment_pages(&self, page_size: u64) -> u64 {
-        (self.segment_memory_size() + page_size - 1) / page_size
+        // `size + page_size - 1` would overflow for a segment near the top of the address space.
+        let size = self.segment_memory_size();
+        size / page_size + (size % page_size != 0) as u64
     }
 }
 
@@ -108,7 +118,10 @@ pub struct ProgramHeaderIter<'a> {
 
 impl<'a> ProgramHeaderIter<'a> {
     pub fn new(buffer: &'a [u8], entries: usize, entry_size: usize) -> Result<Self> {
-        if buffer.len() >= entries * entry_size {
+        if entry_size < core::mem::size_of::<ProgramHeader>() {
+            return Err(Error::InvalidEntrySize(entry_size));
+        }
+        if entries.checked_mul(entry_size).map_or(false, |size| buffer.len() >= size) {
             Ok(Self {
                 buffer,
                 entries,
@@ -130,7 +143,7 @@ impl<'a> Iterator for ProgramHeaderIter<'a> {
             self.index += 1;
             let buffer_end = self.entry_size * self.index;
             let program_header = unsafe {
-                ((&self.buffer[buffer_start..buffer_end]).as_ptr() as *const ProgramHeader)
+                (self.buffer[buffer_start..buffer_end].as_ptr() as *const ProgramHeader)
                     .as_ref()
                     .unwrap()
             };
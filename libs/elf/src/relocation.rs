@@ -0,0 +1,76 @@
+use core::fmt;
+
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct RelocationType(u32);
+
+impl RelocationType {
+    pub const X86_64_NONE: Self = Self(0);
+    pub const X86_64_64: Self = Self(1);
+    pub const X86_64_GLOB_DAT: Self = Self(6);
+    pub const X86_64_RELATIVE: Self = Self(8);
+}
+
+impl fmt::Debug for RelocationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::X86_64_NONE => write!(f, "R_X86_64_NONE"),
+            Self::X86_64_64 => write!(f, "R_X86_64_64"),
+            Self::X86_64_GLOB_DAT => write!(f, "R_X86_64_GLOB_DAT"),
+            Self::X86_64_RELATIVE => write!(f, "R_X86_64_RELATIVE"),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct Rela {
+    offset: u64,
+    info: u64,
+    addend: i64,
+}
+
+impl Rela {
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn symbol_index(&self) -> u32 {
+        (self.info >> 32) as u32
+    }
+
+    pub fn relocation_type(&self) -> RelocationType {
+        RelocationType(self.info as u32)
+    }
+
+    pub fn addend(&self) -> i64 {
+        self.addend
+    }
+}
+
+pub struct RelaIter<'a> {
+    buffer: &'a [u8],
+    entry_size: usize,
+    index: usize,
+}
+
+impl<'a> RelaIter<'a> {
+    pub(crate) fn new(buffer: &'a [u8], entry_size: usize) -> Self {
+        Self { buffer, entry_size, index: 0 }
+    }
+}
+
+impl<'a> Iterator for RelaIter<'a> {
+    type Item = &'a Rela;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.entry_size * self.index;
+        let end = start + core::mem::size_of::<Rela>();
+        if self.entry_size < core::mem::size_of::<Rela>() || end > self.buffer.len() {
+            return None;
+        }
+        self.index += 1;
+        unsafe { (self.buffer[start..end].as_ptr() as *const Rela).as_ref() }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::result::Result;
+use crate::error::Error;
+use core::ops::BitOr;
+use core::fmt;
+
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct SectionType(u32);
+
+impl SectionType {
+    pub const NULL: Self = Self(0);
+    pub const PROGRAM_BITS: Self = Self(1);
+    pub const SYMBOL_TABLE: Self = Self(2);
+    pub const STRING_TABLE: Self = Self(3);
+    pub const RELA: Self = Self(4);
+    pub const HASH: Self = Self(5);
+    pub const DYNAMIC: Self = Self(6);
+    pub const NOTE: Self = Self(7);
+    pub const NO_BITS: Self = Self(8);
+    pub const REL: Self = Self(9);
+    pub const DYNAMIC_SYMBOL_TABLE: Self = Self(11);
+    pub const INIT_ARRAY: Self = Self(14);
+    pub const FINI_ARRAY: Self = Self(15);
+    pub const GNU_HASH: Self = Self(0x6ffffff6);
+}
+
+impl fmt::Debug for SectionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NULL => write!(f, "Null"),
+            Self::PROGRAM_BITS => write!(f, "ProgramBits"),
+            Self::SYMBOL_TABLE => write!(f, "SymbolTable"),
+            Self::STRING_TABLE => write!(f, "StringTable"),
+            Self::RELA => write!(f, "Rela"),
+            Self::HASH => write!(f, "Hash"),
+            Self::DYNAMIC => write!(f, "Dynamic"),
+            Self::NOTE => write!(f, "Note"),
+            Self::NO_BITS => write!(f, "NoBits"),
+            Self::REL => write!(f, "Rel"),
+            Self::DYNAMIC_SYMBOL_TABLE => write!(f, "DynamicSymbolTable"),
+            Self::INIT_ARRAY => write!(f, "InitArray"),
+            Self::FINI_ARRAY => write!(f, "FiniArray"),
+            Self::GNU_HASH => write!(f, "GnuHash"),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
+}
+
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SectionHeaderFlags(u64);
+
+impl SectionHeaderFlags {
+    pub const WRITABLE: Self = Self(1 << 0);
+    pub const ALLOCATE: Self = Self(1 << 1);
+    pub const EXECUTABLE: Self = Self(1 << 2);
+
+    pub fn contains(self, flags: Self) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+}
+
+impl BitOr for SectionHeaderFlags {
+    type Output = SectionHeaderFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct SectionHeader {
+    name: u32,
+    section_type: SectionType,
+    flags: SectionHeaderFlags,
+    address: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    info: u32,
+    alignment: u64,
+    entry_size: u64,
+}
+
+impl SectionHeader {
+    pub fn name_offset(&self) -> u32 {
+        self.name
+    }
+
+    pub fn section_type(&self) -> SectionType {
+        self.section_type
+    }
+
+    pub fn flags(&self) -> SectionHeaderFlags {
+        self.flags
+    }
+
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn link(&self) -> u32 {
+        self.link
+    }
+
+    pub fn info(&self) -> u32 {
+        self.info
+    }
+
+    pub fn alignment(&self) -> u64 {
+        self.alignment
+    }
+
+    pub fn entry_size(&self) -> u64 {
+        self.entry_size
+    }
+}
+
+pub struct SectionHeaderIter<'a> {
+    buffer: &'a [u8],
+    entries: usize,
+    entry_size: usize,
+    index: usize,
+}
+
+impl<'a> SectionHeaderIter<'a> {
+    pub fn new(buffer: &'a [u8], entries: usize, entry_size: usize) -> Result<Self> {
+        if entry_size < core::mem::size_of::<SectionHeader>() {
+            return Err(Error::InvalidEntrySize(entry_size));
+        }
+        if entries.checked_mul(entry_size).map_or(false, |size| buffer.len() >= size) {
+            Ok(Self {
+                buffer,
+                entries,
+                entry_size,
+                index: 0,
+            })
+        } else {
+            Err(Error::BufferSizeTooSmall)
+        }
+    }
+}
+
+impl<'a> Iterator for SectionHeaderIter<'a> {
+    type Item = &'a SectionHeader;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.entries > self.index {
+            let buffer_start = self.entry_size * self.index;
+            self.index += 1;
+            let buffer_end = buffer_start + core::mem::size_of::<SectionHeader>();
+            let section_header = unsafe {
+                (self.buffer[buffer_start..buffer_end].as_ptr() as *const SectionHeader)
+                    .as_ref()
+                    .unwrap()
+            };
+            Some(section_header)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use core::str;
+
+#[derive(Copy, Clone)]
+pub struct StringTable<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> StringTable<'a> {
+    pub fn new(buffer: &'a [u8]) -> Self {
+        Self { buffer }
+    }
+
+    pub fn get(&self, offset: u32) -> Option<&'a str> {
+        let bytes = self.buffer.get(offset as usize..)?;
+        let length = bytes.iter().position(|&byte| byte == 0)?;
+        str::from_utf8(&bytes[..length]).ok()
+    }
+}
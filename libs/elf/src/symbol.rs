@@ -0,0 +1,157 @@
+use crate::StringTable;
+use core::fmt;
+
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct SymbolBinding(u8);
+
+impl SymbolBinding {
+    pub const LOCAL: Self = Self(0);
+    pub const GLOBAL: Self = Self(1);
+    pub const WEAK: Self = Self(2);
+}
+
+impl fmt::Debug for SymbolBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LOCAL => write!(f, "Local"),
+            Self::GLOBAL => write!(f, "Global"),
+            Self::WEAK => write!(f, "Weak"),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
+}
+
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct SymbolType(u8);
+
+impl SymbolType {
+    pub const NO_TYPE: Self = Self(0);
+    pub const OBJECT: Self = Self(1);
+    pub const FUNCTION: Self = Self(2);
+    pub const SECTION: Self = Self(3);
+    pub const FILE: Self = Self(4);
+    pub const COMMON: Self = Self(5);
+    pub const TLS: Self = Self(6);
+}
+
+impl fmt::Debug for SymbolType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NO_TYPE => write!(f, "NoType"),
+            Self::OBJECT => write!(f, "Object"),
+            Self::FUNCTION => write!(f, "Function"),
+            Self::SECTION => write!(f, "Section"),
+            Self::FILE => write!(f, "File"),
+            Self::COMMON => write!(f, "Common"),
+            Self::TLS => write!(f, "TLS"),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
+}
+
+pub const SECTION_INDEX_UNDEFINED: u16 = 0;
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct Symbol {
+    name: u32,
+    info: u8,
+    other: u8,
+    section_index: u16,
+    value: u64,
+    size: u64,
+}
+
+impl Symbol {
+    pub fn name_offset(&self) -> u32 {
+        self.name
+    }
+
+    pub fn binding(&self) -> SymbolBinding {
+        SymbolBinding(self.info >> 4)
+    }
+
+    pub fn symbol_type(&self) -> SymbolType {
+        SymbolType(self.info & 0xf)
+    }
+
+    pub fn section_index(&self) -> u16 {
+        self.section_index
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        self.section_index == SECTION_INDEX_UNDEFINED
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+pub struct SymbolIter<'a> {
+    buffer: &'a [u8],
+    entry_size: usize,
+    index: usize,
+}
+
+impl<'a> SymbolIter<'a> {
+    pub(crate) fn new(buffer: &'a [u8], entry_size: usize) -> Self {
+        Self { buffer, entry_size, index: 0 }
+    }
+}
+
+impl<'a> Iterator for SymbolIter<'a> {
+    type Item = &'a Symbol;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.entry_size * self.index;
+        let end = start + core::mem::size_of::<Symbol>();
+        if self.entry_size < core::mem::size_of::<Symbol>() || end > self.buffer.len() {
+            return None;
+        }
+        self.index += 1;
+        unsafe { (self.buffer[start..end].as_ptr() as *const Symbol).as_ref() }
+    }
+}
+
+pub struct SymbolTable<'a> {
+    buffer: &'a [u8],
+    entry_size: usize,
+    strings: StringTable<'a>,
+}
+
+impl<'a> SymbolTable<'a> {
+    pub(crate) fn new(buffer: &'a [u8], entry_size: usize, strings: StringTable<'a>) -> Self {
+        Self { buffer, entry_size, strings }
+    }
+
+    pub fn iter(&self) -> SymbolIter<'a> {
+        SymbolIter::new(self.buffer, self.entry_size)
+    }
+
+    pub fn name(&self, symbol: &Symbol) -> Option<&'a str> {
+        self.strings.get(symbol.name_offset())
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&'a Symbol> {
+        self.iter().find(|symbol| self.name(symbol) == Some(name))
+    }
+
+    pub fn find_by_address(&self, address: u64) -> Option<(&'a Symbol, &'a str)> {
+        self.iter()
+            .filter(|symbol| !symbol.is_undefined())
+            .filter(|symbol| symbol.symbol_type() == SymbolType::FUNCTION
+                || symbol.symbol_type() == SymbolType::OBJECT)
+            .find(|symbol| symbol.value() <= address
+                && symbol.value()
+                    .checked_add(symbol.size().max(1))
+                    .map_or(false, |end| address < end))
+            .and_then(|symbol| Some((symbol, self.name(symbol)?)))
+    }
+}
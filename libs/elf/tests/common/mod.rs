@@ -0,0 +1,277 @@
+#![allow(dead_code)]
+
+use std::ops::{Deref, DerefMut};
+
+pub const DATA_ADDRESS: u64 = 0x200;
+pub const DATA_SIZE: u64 = 0x100;
+
+const FILE_HEADER_SIZE: usize = 64;
+const PROGRAM_HEADER_SIZE: usize = 56;
+const RELA_SIZE: usize = 24;
+const SYMBOL_SIZE: usize = 24;
+const DYNAMIC_ENTRY_SIZE: usize = 16;
+const SECTION_HEADER_SIZE: usize = 64;
+
+pub const PT_LOAD: u32 = 1;
+pub const PT_DYNAMIC: u32 = 2;
+pub const PT_NOTE: u32 = 4;
+pub const PF_RW: u32 = 0b110;
+
+pub const SHT_NOTE: u32 = 7;
+
+pub const DT_RELA: i64 = 7;
+pub const DT_RELASZ: i64 = 8;
+pub const DT_RELAENT: i64 = 9;
+pub const DT_SYMTAB: i64 = 6;
+pub const DT_SYMENT: i64 = 11;
+
+pub const R_X86_64_64: u32 = 1;
+pub const R_X86_64_GLOB_DAT: u32 = 6;
+pub const R_X86_64_RELATIVE: u32 = 8;
+
+pub const STB_GLOBAL: u8 = 1;
+pub const STB_WEAK: u8 = 2;
+
+pub struct AlignedBuffer {
+    words: Vec<u64>,
+    length: usize,
+}
+
+impl AlignedBuffer {
+    pub fn new(bytes: &[u8]) -> Self {
+        let mut buffer = Self { words: vec![0; bytes.len() / 8 + 1], length: bytes.len() };
+        buffer.copy_from_slice(bytes);
+        buffer
+    }
+
+    pub fn truncated(&self, length: usize) -> Self {
+        Self::new(&self[..length])
+    }
+
+    pub fn write_u16(&mut self, offset: usize, value: u16) {
+        self[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, offset: usize, value: u32) {
+        self[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, offset: usize, value: u64) {
+        self[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn read_u64(&self, offset: usize) -> u64 {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::slice::from_raw_parts(self.words.as_ptr() as *const u8, self.length) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { std::slice::from_raw_parts_mut(self.words.as_mut_ptr() as *mut u8, self.length) }
+    }
+}
+
+pub fn tiny_elf() -> AlignedBuffer {
+    AlignedBuffer::new(include_bytes!("../data/tiny.elf"))
+}
+
+#[derive(Copy, Clone)]
+pub struct Segment {
+    pub segment_type: u32,
+    pub offset: u64,
+    pub address: u64,
+    pub file_size: u64,
+    pub memory_size: u64,
+}
+
+pub struct ElfBuilder {
+    file_type: u16,
+    machine: u16,
+    version: u32,
+    segments: Vec<Segment>,
+    relocations: Vec<(u64, u32, u32, i64)>,
+    symbols: Vec<(u8, u16, u64)>,
+    notes: Vec<u8>,
+    section_notes: Vec<u8>,
+}
+
+// Builds a position-independent image whose single LOAD segment maps the whole file at
+// address 0, so virtual addresses equal file offsets. DATA_ADDRESS is free for relocations.
+impl ElfBuilder {
+    pub fn new() -> Self {
+        Self {
+            file_type: 3,
+            machine: 62,
+            version: 1,
+            segments: Vec::new(),
+            relocations: Vec::new(),
+            symbols: Vec::new(),
+            notes: Vec::new(),
+            section_notes: Vec::new(),
+        }
+    }
+
+    pub fn file_type(mut self, file_type: u16) -> Self {
+        self.file_type = file_type;
+        self
+    }
+
+    pub fn machine(mut self, machine: u16) -> Self {
+        self.machine = machine;
+        self
+    }
+
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    pub fn relocation(mut self, offset: u64, relocation_type: u32, symbol: u32, addend: i64)
+                      -> Self {
+        self.relocations.push((offset, relocation_type, symbol, addend));
+        self
+    }
+
+    pub fn symbol(mut self, binding: u8, section_index: u16, value: u64) -> Self {
+        self.symbols.push((binding, section_index, value));
+        self
+    }
+
+    // Adds a note to a PT_NOTE segment.
+    pub fn note(mut self, note: &[u8]) -> Self {
+        self.notes.extend_from_slice(note);
+        self
+    }
+
+    // Adds a note to a SHT_NOTE section, which is not covered by any PT_NOTE segment.
+    pub fn section_note(mut self, note: &[u8]) -> Self {
+        self.section_notes.extend_from_slice(note);
+        self
+    }
+
+    pub fn build(self) -> AlignedBuffer {
+        let dynamic = !self.relocations.is_empty() || !self.symbols.is_empty();
+        let rela_offset = (DATA_ADDRESS + DATA_SIZE) as usize;
+        let symbol_offset = rela_offset + self.relocations.len() * RELA_SIZE;
+        let dynamic_offset = symbol_offset + (self.symbols.len() + 1) * SYMBOL_SIZE;
+        let dynamic_entries = [
+            (DT_RELA, rela_offset as u64),
+            (DT_RELASZ, (self.relocations.len() * RELA_SIZE) as u64),
+            (DT_RELAENT, RELA_SIZE as u64),
+            (DT_SYMTAB, symbol_offset as u64),
+            (DT_SYMENT, SYMBOL_SIZE as u64),
+            (0, 0),
+        ];
+        let note_offset = dynamic_offset + dynamic_entries.len() * DYNAMIC_ENTRY_SIZE;
+        let section_note_offset = note_offset + self.notes.len();
+        let section_header_offset = (section_note_offset + self.section_notes.len() + 7) & !7;
+        let sections = if self.section_notes.is_empty() { 0 } else { 2 };
+        let length = section_header_offset + sections * SECTION_HEADER_SIZE;
+
+        let mut segments = vec![Segment {
+            segment_type: PT_LOAD,
+            offset: 0,
+            address: 0,
+            file_size: length as u64,
+            memory_size: length as u64,
+        }];
+        if dynamic {
+            segments.push(Segment {
+                segment_type: PT_DYNAMIC,
+                offset: dynamic_offset as u64,
+                address: dynamic_offset as u64,
+                file_size: (dynamic_entries.len() * DYNAMIC_ENTRY_SIZE) as u64,
+                memory_size: (dynamic_entries.len() * DYNAMIC_ENTRY_SIZE) as u64,
+            });
+        }
+        if !self.notes.is_empty() {
+            segments.push(Segment {
+                segment_type: PT_NOTE,
+                offset: note_offset as u64,
+                address: note_offset as u64,
+                file_size: self.notes.len() as u64,
+                memory_size: self.notes.len() as u64,
+            });
+        }
+        segments.extend(self.segments.iter().copied());
+        assert!(FILE_HEADER_SIZE + segments.len() * PROGRAM_HEADER_SIZE <= DATA_ADDRESS as usize);
+
+        let mut buffer = AlignedBuffer::new(&vec![0; length]);
+        buffer[0..8].copy_from_slice(&[0x7f, b'E', b'L', b'F', 2, 1, 1, 0]);
+        buffer.write_u16(16, self.file_type);
+        buffer.write_u16(18, self.machine);
+        buffer.write_u32(20, self.version);
+        buffer.write_u64(32, FILE_HEADER_SIZE as u64);
+        buffer.write_u16(52, FILE_HEADER_SIZE as u16);
+        buffer.write_u16(54, PROGRAM_HEADER_SIZE as u16);
+        buffer.write_u16(56, segments.len() as u16);
+        buffer.write_u16(58, SECTION_HEADER_SIZE as u16);
+        if sections > 0 {
+            buffer.write_u64(40, section_header_offset as u64);
+            buffer.write_u16(60, sections as u16);
+        }
+
+        for (index, segment) in segments.iter().enumerate() {
+            let offset = FILE_HEADER_SIZE + index * PROGRAM_HEADER_SIZE;
+            buffer.write_u32(offset, segment.segment_type);
+            buffer.write_u32(offset + 4, PF_RW);
+            buffer.write_u64(offset + 8, segment.offset);
+            buffer.write_u64(offset + 16, segment.address);
+            buffer.write_u64(offset + 24, segment.address);
+            buffer.write_u64(offset + 32, segment.file_size);
+            buffer.write_u64(offset + 40, segment.memory_size);
+            buffer.write_u64(offset + 48, if segment.segment_type == PT_NOTE { 4 } else { 8 });
+        }
+
+        for (index, &(offset, relocation_type, symbol, addend)) in
+            self.relocations.iter().enumerate() {
+            let entry = rela_offset + index * RELA_SIZE;
+            buffer.write_u64(entry, offset);
+            buffer.write_u64(entry + 8, (symbol as u64) << 32 | relocation_type as u64);
+            buffer.write_u64(entry + 16, addend as u64);
+        }
+
+        for (index, &(binding, section_index, value)) in self.symbols.iter().enumerate() {
+            let entry = symbol_offset + (index + 1) * SYMBOL_SIZE;
+            buffer[entry + 4] = binding << 4;
+            buffer.write_u16(entry + 6, section_index);
+            buffer.write_u64(entry + 8, value);
+        }
+
+        buffer[note_offset..section_note_offset].copy_from_slice(&self.notes);
+        buffer[section_note_offset..section_note_offset + self.section_notes.len()]
+            .copy_from_slice(&self.section_notes);
+        if sections > 0 {
+            // The first section header is the null section.
+            let entry = section_header_offset + SECTION_HEADER_SIZE;
+            buffer.write_u32(entry + 4, SHT_NOTE);
+            buffer.write_u64(entry + 24, section_note_offset as u64);
+            buffer.write_u64(entry + 32, self.section_notes.len() as u64);
+            buffer.write_u64(entry + 48, 4);
+        }
+
+        if dynamic {
+            for (index, &(tag, value)) in dynamic_entries.iter().enumerate() {
+                let entry = dynamic_offset + index * DYNAMIC_ENTRY_SIZE;
+                buffer.write_u64(entry, tag as u64);
+                buffer.write_u64(entry + 8, value);
+            }
+        }
+        buffer
+    }
+}
@@ -0,0 +1,6 @@
+// gcc -nostdlib -static-pie -fPIE -O2 -fno-asynchronous-unwind-tables \
+//     -Wl,--build-id=none -Wl,-z,norelro -o tiny.elf tiny.c
+static int counter = 1;
+int *counter_pointer = &counter;
+int answer(void) { return 42 + *counter_pointer; }
+void _start(void) { for (;;) { answer(); } }
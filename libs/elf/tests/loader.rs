@@ -0,0 +1,179 @@
+mod common;
+
+use common::*;
+use elf::error::Error;
+use elf::loader::ELF64Loader;
+use elf::{FileType, Machine, SegmentType};
+
+#[test]
+fn accepts_minimal_file() {
+    let buffer = ElfBuilder::new().build();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    assert_eq!(loader.file_header().file_type(), FileType::SHARED);
+    assert_eq!(loader.file_header().machine(), Machine::X86_64);
+    assert!(loader.is_position_independent());
+}
+
+#[test]
+fn rejects_truncated_file_header() {
+    let buffer = ElfBuilder::new().build();
+    for length in 0..64 {
+        let truncated = buffer.truncated(length);
+        assert!(matches!(ELF64Loader::new(&truncated), Err(Error::BufferSizeTooSmall)));
+    }
+}
+
+#[test]
+fn rejects_unaligned_buffer() {
+    let buffer = AlignedBuffer::new(&[&[0][..], &ElfBuilder::new().build()[..]].concat());
+    assert!(matches!(ELF64Loader::new(&buffer[1..]), Err(Error::BufferNotAligned)));
+}
+
+#[test]
+fn rejects_bad_identification() {
+    let mut buffer = ElfBuilder::new().build();
+    buffer[0] = 0;
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::NotELF64)));
+
+    let mut buffer = ElfBuilder::new().build();
+    buffer[4] = 1;
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::NotELF64)));
+
+    let mut buffer = ElfBuilder::new().build();
+    buffer[5] = 2;
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::UnsupportedDataEncoding(2))));
+}
+
+#[test]
+fn rejects_unsupported_header_fields() {
+    let buffer = ElfBuilder::new().file_type(0xfe00).build();
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::UnsupportedFileType(0xfe00))));
+
+    let buffer = ElfBuilder::new().machine(183).build();
+    assert!(matches!(ELF64Loader::new(&buffer),
+                     Err(Error::UnsupportedMachine(Machine::AARCH64))));
+
+    let buffer = ElfBuilder::new().version(2).build();
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::UnsupportedVersion(2))));
+}
+
+#[test]
+fn rejects_invalid_program_header_table() {
+    let mut buffer = ElfBuilder::new().build();
+    buffer.write_u16(54, 32);
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::InvalidEntrySize(32))));
+
+    let mut buffer = ElfBuilder::new().build();
+    buffer.write_u64(32, 68);
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::TableNotAligned(68))));
+
+    let mut buffer = ElfBuilder::new().build();
+    buffer.write_u64(32, u64::MAX - 7);
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::OffsetOverflow)));
+
+    let mut buffer = ElfBuilder::new().build();
+    buffer.write_u16(56, 0xffff);
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::BufferSizeTooSmall)));
+}
+
+#[test]
+fn rejects_invalid_segments() {
+    let segment = Segment {
+        segment_type: PT_LOAD,
+        offset: 0,
+        address: 0x10000,
+        file_size: 0x10,
+        memory_size: 0x20,
+    };
+    let buffer = ElfBuilder::new().segment(Segment { file_size: 0x30, ..segment }).build();
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::FileSizeExceedsMemorySize(0x10000))));
+
+    let buffer = ElfBuilder::new()
+        .segment(Segment { offset: 0x1_0000_0000, ..segment })
+        .build();
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::BufferSizeTooSmall)));
+
+    let buffer = ElfBuilder::new()
+        .segment(Segment { address: u64::MAX, memory_size: 0x20, ..segment })
+        .build();
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::AddressOverflow(_))));
+
+    let buffer = ElfBuilder::new()
+        .segment(Segment { address: 0x100, memory_size: 0x20, ..segment })
+        .build();
+    assert!(matches!(ELF64Loader::new(&buffer), Err(Error::OverlappingSegments(0))));
+}
+
+#[test]
+fn accepts_adjacent_segments() {
+    let buffer = ElfBuilder::new()
+        .segment(Segment {
+            segment_type: PT_LOAD,
+            offset: 0,
+            address: 0x10000,
+            file_size: 0x10,
+            memory_size: 0x1000,
+        })
+        .segment(Segment {
+            segment_type: PT_LOAD,
+            offset: 0,
+            address: 0x11000,
+            file_size: 0x10,
+            memory_size: 0x1000,
+        })
+        .build();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    let loads = loader.program_header_iter().unwrap()
+        .filter(|program_header| program_header.segment_type() == SegmentType::LOAD)
+        .count();
+    assert_eq!(loads, 3);
+}
+
+#[test]
+fn reports_missing_tables() {
+    let mut buffer = ElfBuilder::new().build();
+    buffer.write_u16(56, 0);
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    assert!(matches!(loader.program_header_iter(), Err(Error::ProgramHeaderNotExist)));
+    assert!(matches!(loader.section_header_iter(), Err(Error::SectionHeaderNotExist)));
+    assert!(matches!(loader.dynamic_entries(), Err(Error::ProgramHeaderNotExist)));
+
+    let buffer = ElfBuilder::new().build();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    assert!(matches!(loader.dynamic_entries(), Err(Error::DynamicSegmentNotExist)));
+}
+
+#[test]
+fn loads_program_and_clears_bss() {
+    let buffer = ElfBuilder::new()
+        .segment(Segment {
+            segment_type: PT_LOAD,
+            offset: 0,
+            address: 0x10000,
+            file_size: 0x8,
+            memory_size: 0x20,
+        })
+        .build();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    let program_header = loader.program_header_iter().unwrap()
+        .find(|program_header| program_header.start_address() == 0x10000)
+        .unwrap();
+
+    let mut memory = [0xccu8; 0x20];
+    unsafe { loader.load_program_at(program_header, memory.as_mut_ptr() as usize); }
+    assert_eq!(&memory[..8], &buffer[..8]);
+    assert!(memory[8..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn never_panics_on_truncated_files() {
+    let buffer = tiny_elf();
+    for length in 0..buffer.len() {
+        let truncated = buffer.truncated(length);
+        if let Ok(loader) = ELF64Loader::new(&truncated) {
+            let _ = loader.relocations().map(|relocations| relocations.count());
+            let _ = loader.symbol_table().map(|symbols| symbols.iter().count());
+            let _ = loader.section_by_name(".text");
+        }
+    }
+}
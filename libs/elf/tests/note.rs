@@ -0,0 +1,88 @@
+mod common;
+
+use common::*;
+use elf::loader::ELF64Loader;
+use elf::{NoteIter, NoteType};
+
+fn note(name: &[u8], note_type: u32, descriptor: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(descriptor.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&note_type.to_le_bytes());
+    bytes.extend_from_slice(name);
+    bytes.resize((bytes.len() + 3) & !3, 0);
+    bytes.extend_from_slice(descriptor);
+    bytes.resize((bytes.len() + 3) & !3, 0);
+    bytes
+}
+
+#[test]
+fn iterates_notes() {
+    let buffer = [
+        note(b"GNU\0", 3, &[0xde, 0xad, 0xbe, 0xef, 0x01]),
+        note(b"MonorsOS\0", 1, &7u32.to_le_bytes()),
+    ].concat();
+    let notes = NoteIter::new(&buffer, 4).collect::<Vec<_>>();
+    assert_eq!(notes.len(), 2);
+
+    assert_eq!(notes[0].name(), Some("GNU"));
+    assert_eq!(notes[0].note_type(), NoteType::GNU_BUILD_ID);
+    assert_eq!(notes[0].build_id(), Some(&[0xde, 0xad, 0xbe, 0xef, 0x01][..]));
+    assert_eq!(notes[0].boot_protocol_version(), None);
+
+    assert_eq!(notes[1].name(), Some("MonorsOS"));
+    assert_eq!(notes[1].boot_protocol_version(), Some(7));
+    assert_eq!(notes[1].build_id(), None);
+}
+
+#[test]
+fn stops_at_truncated_note() {
+    let buffer = note(b"GNU\0", 3, &[0; 20]);
+    assert_eq!(NoteIter::new(&buffer[..buffer.len() - 1], 4).count(), 0);
+    assert_eq!(NoteIter::new(&buffer[..8], 4).count(), 0);
+
+    let mut buffer = buffer;
+    buffer[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(NoteIter::new(&buffer, 4).count(), 0);
+}
+
+#[test]
+fn reports_missing_notes() {
+    let buffer = tiny_elf();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    assert_eq!(loader.notes().unwrap().count(), 0);
+    assert_eq!(loader.build_id(), None);
+    assert_eq!(loader.boot_protocol_version(), None);
+}
+
+#[test]
+fn finds_notes_in_note_segments() {
+    let buffer = ElfBuilder::new()
+        .note(&note(b"GNU\0", 3, &[0xde, 0xad, 0xbe, 0xef]))
+        .note(&note(b"MonorsOS\0", 1, &5u32.to_le_bytes()))
+        .build();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+
+    let notes = loader.notes().unwrap().collect::<Vec<_>>();
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes[0].name(), Some("GNU"));
+    assert_eq!(notes[1].name(), Some("MonorsOS"));
+    assert_eq!(loader.build_id(), Some(&[0xde, 0xad, 0xbe, 0xef][..]));
+    assert_eq!(loader.boot_protocol_version(), Some(5));
+    assert!(loader.section_notes().is_err());
+}
+
+#[test]
+fn finds_notes_in_note_sections() {
+    let buffer = ElfBuilder::new()
+        .section_note(&note(b"MonorsOS\0", 1, &5u32.to_le_bytes()))
+        .build();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+
+    let notes = loader.section_notes().unwrap().collect::<Vec<_>>();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].boot_protocol_version(), Some(5));
+    // Only notes in loaded segments are looked up by the loader.
+    assert_eq!(loader.notes().unwrap().count(), 0);
+    assert_eq!(loader.boot_protocol_version(), None);
+}
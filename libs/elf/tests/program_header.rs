@@ -0,0 +1,50 @@
+mod common;
+
+use common::*;
+use elf::error::Error;
+use elf::{ProgramHeaderFlags, ProgramHeaderIter, SegmentType};
+
+#[test]
+fn iterates_entries() {
+    let buffer = ElfBuilder::new().relocation(DATA_ADDRESS, R_X86_64_RELATIVE, 0, 0).build();
+    let iter = ProgramHeaderIter::new(&buffer[64..176], 2, 56).unwrap();
+    let types = iter.map(|program_header| program_header.segment_type()).collect::<Vec<_>>();
+    assert_eq!(types, [SegmentType::LOAD, SegmentType::DYNAMIC]);
+}
+
+#[test]
+fn stops_after_entries() {
+    let buffer = ElfBuilder::new().relocation(DATA_ADDRESS, R_X86_64_RELATIVE, 0, 0).build();
+    assert_eq!(ProgramHeaderIter::new(&buffer[64..176], 1, 56).unwrap().count(), 1);
+}
+
+#[test]
+fn reads_fields() {
+    let buffer = ElfBuilder::new().build();
+    let program_header = ProgramHeaderIter::new(&buffer[64..120], 1, 56).unwrap()
+        .next()
+        .unwrap();
+    assert_eq!(program_header.offset(), 0);
+    assert_eq!(program_header.start_address(), 0);
+    assert_eq!(program_header.segment_file_size(), buffer.len() as u64);
+    assert_eq!(program_header.segment_pages(0x1000), 1);
+    assert!(program_header.flags().contains(ProgramHeaderFlags::READABLE));
+    assert!(program_header.flags().contains(ProgramHeaderFlags::WRITABLE));
+    assert!(!program_header.flags().contains(ProgramHeaderFlags::EXECUTABLE));
+}
+
+#[test]
+fn rejects_short_buffer() {
+    let buffer = ElfBuilder::new().build();
+    assert!(matches!(ProgramHeaderIter::new(&buffer[64..119], 1, 56),
+                     Err(Error::BufferSizeTooSmall)));
+    assert!(matches!(ProgramHeaderIter::new(&buffer[64..120], usize::MAX, 56),
+                     Err(Error::BufferSizeTooSmall)));
+}
+
+#[test]
+fn rejects_small_entry_size() {
+    let buffer = ElfBuilder::new().build();
+    assert!(matches!(ProgramHeaderIter::new(&buffer[64..120], 1, 48),
+                     Err(Error::InvalidEntrySize(48))));
+}
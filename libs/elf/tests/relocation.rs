@@ -0,0 +1,87 @@
+mod common;
+
+use std::cell::RefCell;
+
+use common::*;
+use elf::error::Error;
+use elf::loader::ELF64Loader;
+use elf::RelocationType;
+
+const LOAD_BIAS: u64 = 0xffff_ffff_8000_0000;
+
+fn relocate(buffer: &AlignedBuffer) -> Result<AlignedBuffer, Error> {
+    let loader = ELF64Loader::new(buffer)?;
+    let image = RefCell::new(AlignedBuffer::new(buffer));
+    unsafe {
+        loader.apply_relocations(LOAD_BIAS, |address| {
+            image.borrow_mut().get_mut(address as usize..).map(|bytes| bytes.as_mut_ptr())
+        })?;
+    }
+    Ok(image.into_inner())
+}
+
+#[test]
+fn lists_relocations() {
+    let buffer = ElfBuilder::new()
+        .relocation(DATA_ADDRESS, R_X86_64_RELATIVE, 0, 0x10)
+        .relocation(DATA_ADDRESS + 8, R_X86_64_64, 1, 0)
+        .symbol(STB_GLOBAL, 1, 0x40)
+        .build();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    let relocations = loader.relocations().unwrap().collect::<Vec<_>>();
+    assert_eq!(relocations.len(), 2);
+    assert_eq!(relocations[0].offset(), DATA_ADDRESS);
+    assert_eq!(relocations[0].relocation_type(), RelocationType::X86_64_RELATIVE);
+    assert_eq!(relocations[0].addend(), 0x10);
+    assert_eq!(relocations[1].symbol_index(), 1);
+    assert_eq!(relocations[1].relocation_type(), RelocationType::X86_64_64);
+}
+
+#[test]
+fn applies_relative() {
+    let buffer = ElfBuilder::new().relocation(DATA_ADDRESS, R_X86_64_RELATIVE, 0, 0x1234).build();
+    let image = relocate(&buffer).unwrap();
+    assert_eq!(image.read_u64(DATA_ADDRESS as usize), LOAD_BIAS + 0x1234);
+}
+
+#[test]
+fn applies_symbol_relocations() {
+    let buffer = ElfBuilder::new()
+        .relocation(DATA_ADDRESS, R_X86_64_64, 1, 0x8)
+        .relocation(DATA_ADDRESS + 8, R_X86_64_GLOB_DAT, 1, 0x8)
+        .relocation(DATA_ADDRESS + 16, R_X86_64_64, 2, 0x8)
+        .symbol(STB_GLOBAL, 1, 0x40)
+        .symbol(STB_WEAK, 0, 0)
+        .build();
+    let image = relocate(&buffer).unwrap();
+    assert_eq!(image.read_u64(DATA_ADDRESS as usize), LOAD_BIAS + 0x48);
+    assert_eq!(image.read_u64(DATA_ADDRESS as usize + 8), LOAD_BIAS + 0x40);
+    assert_eq!(image.read_u64(DATA_ADDRESS as usize + 16), 0x8);
+}
+
+#[test]
+fn rejects_undefined_symbol() {
+    let buffer = ElfBuilder::new()
+        .relocation(DATA_ADDRESS, R_X86_64_GLOB_DAT, 1, 0)
+        .symbol(STB_GLOBAL, 0, 0)
+        .build();
+    assert!(matches!(relocate(&buffer), Err(Error::UndefinedSymbol(1))));
+}
+
+#[test]
+fn rejects_symbol_out_of_table() {
+    let buffer = ElfBuilder::new().relocation(DATA_ADDRESS, R_X86_64_64, 0x1000, 0).build();
+    assert!(matches!(relocate(&buffer), Err(Error::AddressNotLoaded(_))));
+}
+
+#[test]
+fn rejects_unsupported_relocation() {
+    let buffer = ElfBuilder::new().relocation(DATA_ADDRESS, 7, 0, 0).build();
+    assert!(matches!(relocate(&buffer), Err(Error::UnsupportedRelocation(_))));
+}
+
+#[test]
+fn rejects_target_outside_segments() {
+    let buffer = ElfBuilder::new().relocation(0x10_0000, R_X86_64_RELATIVE, 0, 0).build();
+    assert!(matches!(relocate(&buffer), Err(Error::AddressNotLoaded(0x10_0000))));
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::*;
+use elf::loader::ELF64Loader;
+use elf::{SectionType, SymbolBinding, SymbolType};
+
+#[test]
+fn finds_sections() {
+    let buffer = tiny_elf();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    assert_eq!(loader.section_header_iter().unwrap().count(), 13);
+
+    let text = loader.section_by_name(".text").unwrap();
+    assert_eq!(text.section_type(), SectionType::PROGRAM_BITS);
+    assert_eq!(text.address(), 0x1000);
+    assert_eq!(loader.section_name(text).unwrap(), ".text");
+    assert!(loader.section_by_name(".missing").is_err());
+}
+
+#[test]
+fn iterates_symbols() {
+    let buffer = tiny_elf();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    let symbols = loader.symbol_table().unwrap();
+    assert_eq!(symbols.iter().count(), 11);
+
+    let answer = symbols.find_by_name("answer").unwrap();
+    assert_eq!(answer.value(), 0x1000);
+    assert_eq!(answer.size(), 13);
+    assert_eq!(answer.binding(), SymbolBinding::GLOBAL);
+    assert_eq!(answer.symbol_type(), SymbolType::FUNCTION);
+
+    let counter = symbols.find_by_name("counter").unwrap();
+    assert_eq!(counter.binding(), SymbolBinding::LOCAL);
+    assert_eq!(counter.symbol_type(), SymbolType::OBJECT);
+}
+
+#[test]
+fn symbolizes_addresses() {
+    let buffer = tiny_elf();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    let symbols = loader.symbol_table().unwrap();
+    assert_eq!(symbols.find_by_address(0x1004).map(|(_, name)| name), Some("answer"));
+    assert_eq!(symbols.find_by_address(0x1010).map(|(_, name)| name), Some("_start"));
+    assert!(symbols.find_by_address(0x3000).is_none());
+}
+
+#[test]
+fn symbolizes_addresses_past_overflowing_symbol() {
+    let mut buffer = tiny_elf();
+    let offset = {
+        let loader = ELF64Loader::new(&buffer).unwrap();
+        let answer = loader.symbol_table().unwrap().find_by_name("answer").unwrap();
+        answer as *const _ as usize - buffer.as_ptr() as usize
+    };
+    buffer.write_u64(offset + 16, u64::MAX);
+
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    let symbols = loader.symbol_table().unwrap();
+    assert_eq!(symbols.find_by_address(0x1010).map(|(_, name)| name), Some("_start"));
+    assert!(symbols.find_by_address(0x3000).is_none());
+}
+
+#[test]
+fn relocates_real_binary() {
+    let buffer = tiny_elf();
+    let loader = ELF64Loader::new(&buffer).unwrap();
+    let relocations = loader.relocations().unwrap().collect::<Vec<_>>();
+    assert_eq!(relocations.len(), 1);
+    assert_eq!(relocations[0].offset(), 0x2108);
+    assert_eq!(relocations[0].addend(), 0x2100);
+}
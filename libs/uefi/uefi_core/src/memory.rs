@@ -1,27 +1,63 @@
 use crate::{PhysicalAddress, VirtualAddress};
+use core::fmt;
 use core::ops::BitOr;
 
 pub const ALLOCATE_PAGE_SIZE: usize = 4096;
 
-#[repr(u32)]
-#[derive(Debug, Copy, Clone)]
-pub enum MemoryType {
-    ReservedMemoryType,
-    LoaderCode,
-    LoaderData,
-    BootServicesCode,
-    BootServicesData,
-    RuntimeServicesCode,
-    RuntimeServicesData,
-    ConventionalMemory,
-    UnusableMemory,
-    ACPIReclaimMemory,
-    ACPIMemoryNVS,
-    MemoryMappedIO,
-    MemoryMappedIOPortSpace,
-    PalCode,
-    PersistentMemory,
-    MaxMemoryType,
+#[repr(transparent)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct MemoryType(pub u32);
+
+impl MemoryType {
+    pub const RESERVED_MEMORY_TYPE: Self = Self(0);
+    pub const LOADER_CODE: Self = Self(1);
+    pub const LOADER_DATA: Self = Self(2);
+    pub const BOOT_SERVICES_CODE: Self = Self(3);
+    pub const BOOT_SERVICES_DATA: Self = Self(4);
+    pub const RUNTIME_SERVICES_CODE: Self = Self(5);
+    pub const RUNTIME_SERVICES_DATA: Self = Self(6);
+    pub const CONVENTIONAL_MEMORY: Self = Self(7);
+    pub const UNUSABLE_MEMORY: Self = Self(8);
+    pub const ACPI_RECLAIM_MEMORY: Self = Self(9);
+    pub const ACPI_MEMORY_NVS: Self = Self(10);
+    pub const MEMORY_MAPPED_IO: Self = Self(11);
+    pub const MEMORY_MAPPED_IO_PORT_SPACE: Self = Self(12);
+    pub const PAL_CODE: Self = Self(13);
+    pub const PERSISTENT_MEMORY: Self = Self(14);
+
+    pub const OS_DEFINED_START: u32 = 0x80000000;
+
+    pub const fn os_defined(value: u32) -> Self {
+        Self(Self::OS_DEFINED_START | value)
+    }
+
+    pub fn is_os_defined(self) -> bool {
+        self.0 >= Self::OS_DEFINED_START
+    }
+}
+
+impl fmt::Debug for MemoryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::RESERVED_MEMORY_TYPE => write!(f, "ReservedMemoryType"),
+            Self::LOADER_CODE => write!(f, "LoaderCode"),
+            Self::LOADER_DATA => write!(f, "LoaderData"),
+            Self::BOOT_SERVICES_CODE => write!(f, "BootServicesCode"),
+            Self::BOOT_SERVICES_DATA => write!(f, "BootServicesData"),
+            Self::RUNTIME_SERVICES_CODE => write!(f, "RuntimeServicesCode"),
+            Self::RUNTIME_SERVICES_DATA => write!(f, "RuntimeServicesData"),
+            Self::CONVENTIONAL_MEMORY => write!(f, "ConventionalMemory"),
+            Self::UNUSABLE_MEMORY => write!(f, "UnusableMemory"),
+            Self::ACPI_RECLAIM_MEMORY => write!(f, "ACPIReclaimMemory"),
+            Self::ACPI_MEMORY_NVS => write!(f, "ACPIMemoryNVS"),
+            Self::MEMORY_MAPPED_IO => write!(f, "MemoryMappedIO"),
+            Self::MEMORY_MAPPED_IO_PORT_SPACE => write!(f, "MemoryMappedIOPortSpace"),
+            Self::PAL_CODE => write!(f, "PalCode"),
+            Self::PERSISTENT_MEMORY => write!(f, "PersistentMemory"),
+            _ if self.is_os_defined() => write!(f, "{:#x}(OSDefined)", self.0),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
 }
 
 #[repr(u32)]
@@ -0,0 +1,89 @@
+use crate::PhysicalAddress;
+use crate::status::Status;
+
+#[repr(C)]
+pub struct GraphicsOutputProtocol {
+    pub query_mode: extern "efiapi" fn(
+        this: &GraphicsOutputProtocol,
+        mode_number: u32,
+        size_of_info: &mut usize,
+        info: &mut *const GraphicsOutputModeInformation,
+    ) -> Status,
+
+    pub set_mode: extern "efiapi" fn(this: &GraphicsOutputProtocol, mode_number: u32) -> Status,
+
+    pub blt: extern "efiapi" fn(
+        this: &GraphicsOutputProtocol,
+        blt_buffer: *mut BltPixel,
+        blt_operation: BltOperation,
+        source_x: usize,
+        source_y: usize,
+        destination_x: usize,
+        destination_y: usize,
+        width: usize,
+        height: usize,
+        delta: usize,
+    ) -> Status,
+
+    pub mode: *const GraphicsOutputProtocolMode,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct GraphicsOutputProtocolMode {
+    pub max_mode: u32,
+    pub mode: u32,
+    pub info: *const GraphicsOutputModeInformation,
+    pub size_of_info: usize,
+    pub frame_buffer_base: PhysicalAddress,
+    pub frame_buffer_size: usize,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GraphicsOutputModeInformation {
+    pub version: u32,
+    pub horizontal_resolution: u32,
+    pub vertical_resolution: u32,
+    pub pixel_format: GraphicsPixelFormat,
+    pub pixel_information: PixelBitmask,
+    pub pixels_per_scan_line: u32,
+}
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GraphicsPixelFormat {
+    PixelRedGreenBlueReserved8BitPerColor,
+    PixelBlueGreenRedReserved8BitPerColor,
+    PixelBitMask,
+    PixelBltOnly,
+    PixelFormatMax,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PixelBitmask {
+    pub red_mask: u32,
+    pub green_mask: u32,
+    pub blue_mask: u32,
+    pub reserved_mask: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BltPixel {
+    pub blue: u8,
+    pub green: u8,
+    pub red: u8,
+    pub reserved: u8,
+}
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum BltOperation {
+    VideoFill,
+    VideoToBltBuffer,
+    BufferToVideo,
+    VideoToVideo,
+    OperationMax,
+}
@@ -28,7 +28,7 @@ pub struct SimpleTextOutputProtocol {
     pub set_cursor_position:
     extern "efiapi" fn(this: &SimpleTextOutputProtocol, column: usize, row: usize) -> Status,
 
-    _pad: [usize; 1],
+    pub enable_cursor: extern "efiapi" fn(this: &SimpleTextOutputProtocol, visible: bool) -> Status,
 
     pub mode: *const SimpleTextOutputMode,
 }
@@ -1,2 +1,3 @@
 pub mod console;
 pub mod media;
+pub mod rng;
@@ -0,0 +1,18 @@
+use crate::guid::GUID;
+use crate::status::Status;
+
+#[repr(C)]
+pub struct RngProtocol {
+    pub get_info: extern "efiapi" fn(
+        this: &RngProtocol,
+        rng_algorithm_list_size: &mut usize,
+        rng_algorithm_list: *mut GUID,
+    ) -> Status,
+
+    pub get_rng: extern "efiapi" fn(
+        this: &RngProtocol,
+        rng_algorithm: *const GUID,
+        rng_value_length: usize,
+        rng_value: *mut u8,
+    ) -> Status,
+}
@@ -2,7 +2,9 @@ use crate::table_header::TableHeader;
 use crate::status::Status;
 use crate::time::{Time, TimeCapabilities};
 use crate::memory::MemoryDescriptor;
+use crate::guid::GUID;
 use core::ffi::c_void;
+use core::ops::BitOr;
 
 #[repr(C)]
 pub struct RuntimeServices {
@@ -23,7 +25,31 @@ pub struct RuntimeServices {
         virtual_map: *const MemoryDescriptor,
     ) -> Status,
 
-    _pad: [usize; 5],
+    _pad: usize,
+
+    pub get_variable: extern "efiapi" fn(
+        variable_name: *const u16,
+        vendor_guid: &GUID,
+        attributes: *mut VariableAttributes,
+        data_size: &mut usize,
+        data: *mut c_void,
+    ) -> Status,
+
+    pub get_next_variable_name: extern "efiapi" fn(
+        variable_name_size: &mut usize,
+        variable_name: *mut u16,
+        vendor_guid: &mut GUID,
+    ) -> Status,
+
+    pub set_variable: extern "efiapi" fn(
+        variable_name: *const u16,
+        vendor_guid: &GUID,
+        attributes: VariableAttributes,
+        data_size: usize,
+        data: *const c_void,
+    ) -> Status,
+
+    _pad2: usize,
 
     pub reset_system: extern "efiapi" fn(
         reset_type: ResetType,
@@ -41,3 +67,25 @@ pub enum ResetType {
     Shutdown,
     PlatformSpecific,
 }
+
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct VariableAttributes(pub u32);
+
+impl VariableAttributes {
+    pub const NONE: Self = Self(0);
+    pub const NON_VOLATILE: Self = Self(1 << 0);
+    pub const BOOTSERVICE_ACCESS: Self = Self(1 << 1);
+    pub const RUNTIME_ACCESS: Self = Self(1 << 2);
+    pub const HARDWARE_ERROR_RECORD: Self = Self(1 << 3);
+    pub const TIME_BASED_AUTHENTICATED_WRITE_ACCESS: Self = Self(1 << 5);
+    pub const APPEND_WRITE: Self = Self(1 << 6);
+}
+
+impl BitOr for VariableAttributes {
+    type Output = VariableAttributes;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
@@ -23,7 +23,7 @@ unsafe impl GlobalAlloc for Allocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
         let size = layout.size();
         let align = layout.align();
-        let memory_type = MemoryType::LoaderData;
+        let memory_type = MemoryType::LOADER_DATA;
 
         if align <= 8 {
             boot_services().allocate_pool(memory_type, size)
@@ -128,7 +128,7 @@ impl BootServices {
         loop {
             let size = self.memory_map_size();
             let buffer_pointer =
-                self.allocate_pool(MemoryType::ConventionalMemory, size)
+                self.allocate_pool(MemoryType::LOADER_DATA, size)
                     .expect("Could not allocate pool");
             let buffer =
                 unsafe { slice::from_raw_parts_mut(buffer_pointer, size) };
@@ -49,3 +49,9 @@ pub const FILE_INFO: GUID =
 
 pub const FILE_SYSTEM_INFO: GUID =
     GUID::new((0x09576e93, 0x6d3f, 0x11d2, [0x8e, 0x39, 0x00, 0xa0, 0xc9, 0x69, 0x72, 0x3b]));
+
+pub const GRAPHICS_OUTPUT_PROTOCOL: GUID =
+    GUID::new((0x9042a9de, 0x23dc, 0x4a38, [0x96, 0xfb, 0x7a, 0xde, 0xd0, 0x80, 0x51, 0x6a]));
+
+pub const RNG_PROTOCOL: GUID =
+    GUID::new((0x3152bca5, 0xeade, 0x433d, [0x86, 0x2e, 0xc0, 0x1c, 0xdc, 0x29, 0x1f, 0x44]));
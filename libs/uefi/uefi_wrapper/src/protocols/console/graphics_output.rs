@@ -0,0 +1,121 @@
+use crate::PhysicalAddress;
+use crate::guid::GUID;
+use crate::protocols::Protocol;
+use crate::result::Result;
+use core::ptr;
+
+pub use uefi_core::protocols::console::graphics_output::BltOperation;
+pub use uefi_core::protocols::console::graphics_output::BltPixel;
+pub use uefi_core::protocols::console::graphics_output::GraphicsPixelFormat;
+pub use uefi_core::protocols::console::graphics_output::PixelBitmask;
+
+#[repr(transparent)]
+pub struct GraphicsOutputProtocol(uefi_core::protocols::console::graphics_output::GraphicsOutputProtocol);
+
+impl GraphicsOutputProtocol {
+    pub fn query_mode(&self, mode_number: u32) -> Result<GraphicsOutputModeInformation> {
+        let mut size_of_info = 0;
+        let mut info = ptr::null();
+
+        (self.0.query_mode)(&self.0, mode_number, &mut size_of_info, &mut info)
+            .into_result(())?;
+        let mode_information = GraphicsOutputModeInformation(unsafe { *info });
+        crate::system_table().boot_services().free_pool(info as *mut u8);
+        Ok(mode_information)
+    }
+
+    pub fn set_mode(&self, mode_number: u32) -> Result {
+        (self.0.set_mode)(&self.0, mode_number).into_result(())
+    }
+
+    pub fn blt(
+        &self,
+        buffer: &mut [BltPixel],
+        operation: BltOperation,
+        source: (usize, usize),
+        destination: (usize, usize),
+        size: (usize, usize),
+        delta: usize,
+    ) -> Result {
+        (self.0.blt)(
+            &self.0,
+            buffer.as_mut_ptr(),
+            operation,
+            source.0,
+            source.1,
+            destination.0,
+            destination.1,
+            size.0,
+            size.1,
+            delta,
+        ).into_result(())
+    }
+
+    pub fn fill(&self, pixel: BltPixel, destination: (usize, usize), size: (usize, usize)) -> Result {
+        self.blt(&mut [pixel], BltOperation::VideoFill, (0, 0), destination, size, 0)
+    }
+
+    pub fn mode(&self) -> &GraphicsOutputProtocolMode {
+        unsafe { &*(self.0.mode as *const GraphicsOutputProtocolMode) }
+    }
+}
+
+impl Protocol for GraphicsOutputProtocol {
+    fn guid() -> GUID {
+        crate::guid::GRAPHICS_OUTPUT_PROTOCOL
+    }
+}
+
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct GraphicsOutputProtocolMode(
+    uefi_core::protocols::console::graphics_output::GraphicsOutputProtocolMode);
+
+impl GraphicsOutputProtocolMode {
+    pub fn max_mode(&self) -> u32 {
+        self.0.max_mode
+    }
+
+    pub fn mode_number(&self) -> u32 {
+        self.0.mode
+    }
+
+    pub fn info(&self) -> &GraphicsOutputModeInformation {
+        unsafe { &*(self.0.info as *const GraphicsOutputModeInformation) }
+    }
+
+    pub fn frame_buffer_base(&self) -> PhysicalAddress {
+        self.0.frame_buffer_base
+    }
+
+    pub fn frame_buffer_size(&self) -> usize {
+        self.0.frame_buffer_size
+    }
+}
+
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone)]
+pub struct GraphicsOutputModeInformation(
+    uefi_core::protocols::console::graphics_output::GraphicsOutputModeInformation);
+
+impl GraphicsOutputModeInformation {
+    pub fn horizontal_resolution(&self) -> u32 {
+        self.0.horizontal_resolution
+    }
+
+    pub fn vertical_resolution(&self) -> u32 {
+        self.0.vertical_resolution
+    }
+
+    pub fn pixel_format(&self) -> GraphicsPixelFormat {
+        self.0.pixel_format
+    }
+
+    pub fn pixel_information(&self) -> PixelBitmask {
+        self.0.pixel_information
+    }
+
+    pub fn pixels_per_scan_line(&self) -> u32 {
+        self.0.pixels_per_scan_line
+    }
+}
@@ -1,2 +1,3 @@
+pub mod graphics_output;
 pub mod text_input;
 pub mod text_output;
@@ -1,6 +1,6 @@
 use crate::result::Result;
 use crate::Event;
-use core::mem;
+use core::{fmt, mem};
 
 #[repr(transparent)]
 pub struct SimpleTextInputProtocol(
@@ -24,13 +24,52 @@ impl SimpleTextInputProtocol {
     }
 }
 
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct ScanCode(u16);
+
+impl ScanCode {
+    pub const NULL: Self = Self(0x00);
+    pub const UP: Self = Self(0x01);
+    pub const DOWN: Self = Self(0x02);
+    pub const RIGHT: Self = Self(0x03);
+    pub const LEFT: Self = Self(0x04);
+    pub const HOME: Self = Self(0x05);
+    pub const END: Self = Self(0x06);
+    pub const INSERT: Self = Self(0x07);
+    pub const DELETE: Self = Self(0x08);
+    pub const PAGE_UP: Self = Self(0x09);
+    pub const PAGE_DOWN: Self = Self(0x0a);
+    pub const ESCAPE: Self = Self(0x17);
+}
+
+impl fmt::Debug for ScanCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NULL => write!(f, "Null"),
+            Self::UP => write!(f, "Up"),
+            Self::DOWN => write!(f, "Down"),
+            Self::RIGHT => write!(f, "Right"),
+            Self::LEFT => write!(f, "Left"),
+            Self::HOME => write!(f, "Home"),
+            Self::END => write!(f, "End"),
+            Self::INSERT => write!(f, "Insert"),
+            Self::DELETE => write!(f, "Delete"),
+            Self::PAGE_UP => write!(f, "PageUp"),
+            Self::PAGE_DOWN => write!(f, "PageDown"),
+            Self::ESCAPE => write!(f, "Escape"),
+            _ => write!(f, "{:#x}(Unknown)", self.0)
+        }
+    }
+}
+
 #[repr(transparent)]
 #[derive(Debug, Copy, Clone)]
 pub struct InputKey(uefi_core::protocols::console::text_input::InputKey);
 
 impl InputKey {
-    pub fn scan_code(&self) -> u16 {
-        self.0.scan_code
+    pub fn scan_code(&self) -> ScanCode {
+        ScanCode(self.0.scan_code)
     }
 
     pub fn unicode_char(&self) -> u16 {
@@ -40,6 +40,10 @@ impl SimpleTextOutputProtocol {
         (self.0.set_cursor_position)(&self.0, cursor.column, cursor.row).into_result(())
     }
 
+    pub fn enable_cursor(&self, visible: bool) -> Result {
+        (self.0.enable_cursor)(&self.0, visible).into_result(())
+    }
+
     pub fn mode(&self) -> &SimpleTextOutputMode {
         unsafe { &*(self.0.mode as *const SimpleTextOutputMode) }
     }
@@ -115,6 +119,10 @@ pub struct Geometry {
 }
 
 impl Geometry {
+    pub fn new(column: usize, row: usize) -> Self {
+        Self { column, row }
+    }
+
     pub fn column(&self) -> usize {
         self.column
     }
@@ -2,6 +2,7 @@ use crate::guid::GUID;
 
 pub mod console;
 pub mod media;
+pub mod rng;
 
 pub trait Protocol {
     fn guid() -> GUID;
@@ -0,0 +1,30 @@
+use core::ptr;
+
+use crate::guid::GUID;
+use crate::protocols::Protocol;
+use crate::result::Result;
+
+#[repr(transparent)]
+pub struct RngProtocol(uefi_core::protocols::rng::RngProtocol);
+
+impl RngProtocol {
+    pub fn get_rng(&self, algorithm: Option<&GUID>, buffer: &mut [u8]) -> Result {
+        let algorithm = match algorithm {
+            Some(algorithm) => &algorithm.0 as *const _,
+            None => ptr::null()
+        };
+        (self.0.get_rng)(&self.0, algorithm, buffer.len(), buffer.as_mut_ptr()).into_result(())
+    }
+
+    pub fn random_u64(&self) -> Result<u64> {
+        let mut buffer = [0u8; 8];
+        self.get_rng(None, &mut buffer)?;
+        Ok(u64::from_le_bytes(buffer))
+    }
+}
+
+impl Protocol for RngProtocol {
+    fn guid() -> GUID {
+        crate::guid::RNG_PROTOCOL
+    }
+}
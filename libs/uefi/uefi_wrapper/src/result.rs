@@ -1,3 +1,3 @@
-use uefi_core::status::Error;
+pub use uefi_core::status::Error;
 
 pub type Result<T = ()> = core::result::Result<T, Error>;
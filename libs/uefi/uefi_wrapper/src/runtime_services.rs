@@ -1,10 +1,21 @@
+use crate::guid::GUID;
 use crate::time::Time;
 use crate::result::Result;
-use uefi_core::status::Status;
+use uefi_core::status::{Error, Status};
+use core::ffi::c_void;
 use core::mem;
 use core::ptr;
 
-pub use uefi_core::runtime_services::ResetType;
+pub use uefi_core::runtime_services::{ResetType, VariableAttributes};
+
+#[cfg(not(feature = "alloc"))]
+const VARIABLE_NAME_BUFFER_SIZE: usize = 255;
+
+#[cfg(not(feature = "alloc"))]
+type VariableNameBuffer = [u16; VARIABLE_NAME_BUFFER_SIZE + 1];
+
+#[cfg(feature = "alloc")]
+type VariableNameBuffer = alloc::vec::Vec<u16>;
 
 #[repr(transparent)]
 pub struct RuntimeServices(uefi_core::runtime_services::RuntimeServices);
@@ -22,8 +33,105 @@ impl RuntimeServices {
         (self.0.set_time)(&time.0).into_result(())
     }
 
+    pub fn get_variable(
+        &self,
+        name: &str,
+        vendor: &GUID,
+        buffer: &mut [u8],
+    ) -> Result<(usize, VariableAttributes)> {
+        let name = RuntimeServices::encode_variable_name(name)?;
+        let mut attributes = VariableAttributes::NONE;
+        let mut data_size = buffer.len();
+        let status = (self.0.get_variable)(
+            name.as_ptr(),
+            &vendor.0,
+            &mut attributes,
+            &mut data_size,
+            buffer.as_mut_ptr() as *mut c_void,
+        );
+        status.into_result((data_size, attributes))
+    }
+
+    pub fn get_next_variable_name(&self, name: &mut [u16], vendor: &mut GUID) -> Result<usize> {
+        let mut name_size = name.len() * mem::size_of::<u16>();
+        (self.0.get_next_variable_name)(&mut name_size, name.as_mut_ptr(), &mut vendor.0)
+            .into_result(name_size / mem::size_of::<u16>())
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn variable_names(&self) -> Result<alloc::vec::Vec<(alloc::string::String, GUID)>> {
+        let mut variables = alloc::vec::Vec::new();
+        let mut name: alloc::vec::Vec<u16> = alloc::vec![0; 64];
+        let mut vendor = GUID::new((0, 0, 0, [0; 8]));
+        loop {
+            let mut name_size = name.len() * mem::size_of::<u16>();
+            match (self.0.get_next_variable_name)(
+                &mut name_size,
+                name.as_mut_ptr(),
+                &mut vendor.0,
+            ).into_result(()) {
+                Ok(_) => {}
+                Err(Error::NotFound) => return Ok(variables),
+                Err(Error::BufferTooSmall) => {
+                    name.resize(name_size / mem::size_of::<u16>(), 0);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            }
+            let length = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+            variables.push((alloc::string::String::from_utf16_lossy(&name[..length]), vendor));
+        }
+    }
+
+    pub fn set_variable(
+        &self,
+        name: &str,
+        vendor: &GUID,
+        attributes: VariableAttributes,
+        data: &[u8],
+    ) -> Result {
+        let name = RuntimeServices::encode_variable_name(name)?;
+        (self.0.set_variable)(
+            name.as_ptr(),
+            &vendor.0,
+            attributes,
+            data.len(),
+            data.as_ptr() as *const c_void,
+        ).into_result(())
+    }
+
+    pub fn delete_variable(&self, name: &str, vendor: &GUID) -> Result {
+        let name = RuntimeServices::encode_variable_name(name)?;
+        (self.0.set_variable)(
+            name.as_ptr(),
+            &vendor.0,
+            VariableAttributes::NONE,
+            0,
+            ptr::null(),
+        ).into_result(())
+    }
+
     pub fn reset_system(&self, reset_type: ResetType) -> ! {
         (self.0.reset_system)(reset_type, Status::Success, 0, ptr::null_mut())
     }
-}
 
+    fn encode_variable_name(name: &str) -> Result<VariableNameBuffer> {
+        let mut name_buffer;
+        #[cfg(not(feature = "alloc"))]
+            {
+                if name.encode_utf16().count() > VARIABLE_NAME_BUFFER_SIZE {
+                    return Err(Error::InvalidParameter);
+                }
+                name_buffer = [0; VARIABLE_NAME_BUFFER_SIZE + 1];
+                for (i, char) in name.encode_utf16().enumerate() {
+                    name_buffer[i] = char;
+                }
+            }
+        #[cfg(feature = "alloc")]
+            {
+                name_buffer = name.encode_utf16().collect::<alloc::vec::Vec<u16>>();
+                name_buffer.push(0);
+            }
+        Ok(name_buffer)
+    }
+}